@@ -0,0 +1,112 @@
+//! Lexer throughput on a large generated script, compared with the old
+//! `Vec<char>` lexer in the same run.
+//!
+//! ```sh
+//! cargo bench --bench lexer
+//! ```
+
+extern crate catlox;
+
+mod vec_char_lexer;
+
+use catlox::lexer::{Lexer, Token};
+use std::fs;
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use vec_char_lexer::VecCharLexer;
+
+const TARGET_BYTES: usize = 8 * 1024 * 1024;
+const RUNS: u32 = 10;
+
+fn main() {
+    let (source, used, skipped) = generate();
+    let bytes = source.len() as f64;
+
+    println!(
+        "lexing {:.1} MiB from {} fixtures ({} skipped), {} tokens, best of {} runs",
+        bytes / (1024.0 * 1024.0),
+        used,
+        skipped,
+        Lexer::new(&source).count(),
+        RUNS
+    );
+    let current = measure(|| Lexer::new(&source).count());
+    let baseline = measure(|| VecCharLexer::new(&source).count());
+    report("str lexer", current, bytes);
+    report("Vec<char> lexer", baseline, bytes);
+    println!(
+        "speedup: {:.2}x",
+        baseline.as_secs_f64() / current.as_secs_f64()
+    );
+}
+
+/// The fixtures both lexers can handle, repeated until the script is big
+/// enough to time, along with how many fixtures were used and skipped.
+fn generate() -> (String, usize, usize) {
+    let mut paths: Vec<PathBuf> = fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"))
+        .expect("can't read the fixtures directory")
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+
+    let mut fixtures = String::new();
+    let mut used = 0;
+    for path in &paths {
+        let fixture = fs::read_to_string(path).unwrap();
+        if both_lex(&fixture) {
+            fixtures.push_str(&fixture);
+            fixtures.push('\n');
+            used += 1;
+        }
+    }
+    assert!(used > 0, "no fixture both lexers can handle");
+
+    let mut source = String::with_capacity(TARGET_BYTES + fixtures.len());
+    while source.len() < TARGET_BYTES {
+        source.push_str(&fixtures);
+    }
+    (source, used, paths.len() - used)
+}
+
+/// Whether both lexers get through `source` without errors and split it
+/// into the same tokens. Newer syntax, like `%` or `[`, is an error for the
+/// old one.
+fn both_lex(source: &str) -> bool {
+    let mut current = vec![];
+    for t in Lexer::new(source) {
+        if let Token::Illegal(_) = t.token {
+            return false;
+        }
+        current.push((t.span.start, t.span.end));
+    }
+
+    let mut baseline = vec![];
+    for t in VecCharLexer::new(source) {
+        if let vec_char_lexer::Token::Illegal(_) = t.token {
+            return false;
+        }
+        baseline.push((t.span.start, t.span.end));
+    }
+    current == baseline
+}
+
+fn measure<F: FnMut() -> usize>(mut lex: F) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(lex());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(name: &str, elapsed: Duration, bytes: f64) {
+    println!(
+        "{:>16}: {:>8.2} ms {:>8.1} MiB/s",
+        name,
+        elapsed.as_secs_f64() * 1000.0,
+        bytes / (1024.0 * 1024.0) / elapsed.as_secs_f64()
+    );
+}
@@ -0,0 +1,593 @@
+//! The lexer as it was before it moved to walking the source by byte
+//! offset: the input is collected into a `Vec<char>` and token payloads are
+//! assembled a character at a time. It's a frozen copy, tokens and errors
+//! included, kept as a baseline for the benchmark, so it only understands
+//! the syntax of its day.
+
+#![allow(dead_code)]
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum LexError {
+    UnterminatedString,
+    UnexpectedCharacter(char),
+    MalformedNumber(String),
+    UnknownEscape(String),
+    InvalidUnicodeEscape(String),
+    UnterminatedComment,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Token {
+    // Characters
+    Illegal(LexError),
+    // Identifiers + literals
+    Ident(String),
+    LoxString(String),
+    /// A string segment followed by `${`, the rest of the string comes
+    /// back as a `LoxString` (or another `Interpolation`) after the `}`.
+    Interpolation(String),
+    Number(f64),
+    /// The text of a `///` comment, attached by the parser to the
+    /// declaration that follows it.
+    DocComment(String),
+    // Trivia
+    Whitespace(String),
+    Comment(String),
+    Nil,
+    // Operators
+    Assign,
+    Asterisk,
+    Bang,
+    Dot,
+    Minus,
+    Plus,
+    Slash,
+    // Logic Operators
+    LogicAnd,
+    LogicOr,
+    // Order
+    LessThan,
+    LessEqual,
+    GreaterThan,
+    GreaterEqual,
+    // Equality
+    Equal,
+    NotEqual,
+    // Delimiters
+    Comma,
+    Semicolon,
+    // Parentheses
+    LeftParentheses,
+    RightParentheses,
+    // Braces
+    LeftBrace,
+    RightBrace,
+    // Keywords
+    Class,
+    Else,
+    EOF,
+    False,
+    For,
+    Function,
+    If,
+    Let,
+    Return,
+    Super,
+    This,
+    True,
+    While,
+}
+
+pub fn keyword(s: &str) -> Option<Token> {
+    match s {
+        "and" => Some(Token::LogicAnd),
+        "class" => Some(Token::Class),
+        "else" => Some(Token::Else),
+        "false" => Some(Token::False),
+        "fn" => Some(Token::Function),
+        "for" => Some(Token::For),
+        "if" => Some(Token::If),
+        "let" => Some(Token::Let),
+        "nil" => Some(Token::Nil),
+        "or" => Some(Token::LogicOr),
+        "return" => Some(Token::Return),
+        "super" => Some(Token::Super),
+        "this" => Some(Token::This),
+        "true" => Some(Token::True),
+        "while" => Some(Token::While),
+        &_ => None,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpannedToken {
+    pub token: Token,
+    pub span: Span,
+}
+
+pub struct VecCharLexer {
+    input: Vec<char>,
+    index: usize,
+    offset: usize,
+    line: usize,
+    column: usize,
+    /// One entry per `${` we're inside of, counting the braces opened
+    /// since so we know which `}` goes back to the string.
+    interpolations: Vec<usize>,
+    /// Emit whitespace and comments as tokens instead of skipping them.
+    trivia: bool,
+}
+
+impl Iterator for VecCharLexer {
+    type Item = SpannedToken;
+
+    fn next(&mut self) -> Option<SpannedToken> {
+        if let Some(trivia) = self.skip_whitespace() {
+            return Some(trivia);
+        }
+
+        let (start, line, column) = (self.offset, self.line, self.column);
+        let token = match self.scan_token() {
+            Some(token) => token,
+            None if !self.interpolations.is_empty() => {
+                self.interpolations.clear();
+                Token::Illegal(LexError::UnterminatedString)
+            }
+            None => return None,
+        };
+
+        Some(SpannedToken {
+            token,
+            span: Span {
+                start,
+                end: self.offset,
+                line,
+                column,
+            },
+        })
+    }
+}
+
+impl VecCharLexer {
+    pub fn new(input: &str) -> VecCharLexer {
+        VecCharLexer {
+            input: input.chars().collect(),
+            index: 0,
+            offset: 0,
+            line: 1,
+            column: 1,
+            interpolations: vec![],
+            trivia: false,
+        }
+    }
+
+    /// Skip whitespace and comments, they don't produce tokens. Doc comments
+    /// are left in place for `scan_token`. In lossless mode each run of
+    /// whitespace and each comment is returned as a token instead.
+    ///
+    /// An unterminated block comment swallows the rest of the input and is
+    /// returned as an illegal token.
+    fn skip_whitespace(&mut self) -> Option<SpannedToken> {
+        loop {
+            let (start_index, start, line, column) =
+                (self.index, self.offset, self.line, self.column);
+            let comment = match (self.peek(), self.peek_next()) {
+                (Some(c), _) if is_whitespace(c) => {
+                    while self.peek().map_or(false, is_whitespace) {
+                        self.advance();
+                    }
+                    false
+                }
+                (Some('/'), Some('/')) if self.is_doc_comment() => return None,
+                (Some('/'), Some('/')) => {
+                    while let Some(current_char) = self.peek() {
+                        if current_char == '\n' {
+                            break;
+                        }
+                        self.advance();
+                    }
+                    true
+                }
+                (Some('/'), Some('*')) => {
+                    if !self.block_comment() {
+                        return Some(SpannedToken {
+                            token: Token::Illegal(LexError::UnterminatedComment),
+                            span: Span {
+                                start,
+                                end: self.offset,
+                                line,
+                                column,
+                            },
+                        });
+                    }
+                    true
+                }
+                _ => return None,
+            };
+
+            if self.trivia {
+                let text: String = self.input[start_index..self.index].iter().collect();
+                return Some(SpannedToken {
+                    token: if comment {
+                        Token::Comment(text)
+                    } else {
+                        Token::Whitespace(text)
+                    },
+                    span: Span {
+                        start,
+                        end: self.offset,
+                        line,
+                        column,
+                    },
+                });
+            }
+        }
+    }
+
+    /// `///` starts a doc comment, but `////` and longer are plain comments.
+    fn is_doc_comment(&self) -> bool {
+        self.input.get(self.index + 2) == Some(&'/') && self.input.get(self.index + 3) != Some(&'/')
+    }
+
+    /// Consume a block comment including any nested ones, returns false if
+    /// the input ends before it is closed.
+    fn block_comment(&mut self) -> bool {
+        let mut depth = 0;
+        loop {
+            match (self.peek(), self.peek_next()) {
+                (Some('/'), Some('*')) => {
+                    self.advance();
+                    self.advance();
+                    depth += 1;
+                }
+                (Some('*'), Some('/')) => {
+                    self.advance();
+                    self.advance();
+                    depth -= 1;
+                    if depth == 0 {
+                        return true;
+                    }
+                }
+                (Some(_), _) => {
+                    self.advance();
+                }
+                (None, _) => return false,
+            }
+        }
+    }
+
+    /// The text of a doc comment after its `///`, up to the end of the line.
+    fn doc_comment(&mut self) -> Token {
+        self.advance();
+        self.advance();
+        if self.peek() == Some(' ') {
+            self.advance();
+        }
+        let mut text = String::new();
+        while let Some(next) = self.peek() {
+            if next == '\n' {
+                break;
+            }
+            text.push(next);
+            self.advance();
+        }
+        Token::DocComment(text.trim_end().to_string())
+    }
+
+    fn scan_token(&mut self) -> Option<Token> {
+        match self.advance() {
+            None => None,
+            // Operators
+            Some('+') => Some(Token::Plus),
+            Some('-') => Some(Token::Minus),
+            Some('*') => Some(Token::Asterisk),
+            Some('(') => Some(Token::LeftParentheses),
+            Some(')') => Some(Token::RightParentheses),
+            Some(',') => Some(Token::Comma),
+            Some(';') => Some(Token::Semicolon),
+            Some('{') => {
+                if let Some(depth) = self.interpolations.last_mut() {
+                    *depth += 1;
+                }
+                Some(Token::LeftBrace)
+            }
+            Some('}') => match self.interpolations.pop() {
+                // Closes the interpolation, carry on with the string.
+                Some(0) => Some(self.string()),
+                Some(depth) => {
+                    self.interpolations.push(depth - 1);
+                    Some(Token::RightBrace)
+                }
+                None => Some(Token::RightBrace),
+            },
+            Some('.') => Some(Token::Dot),
+            Some('<') => match self.peek() {
+                Some('=') => {
+                    self.advance();
+                    Some(Token::LessEqual)
+                }
+                _ => Some(Token::LessThan),
+            },
+            Some('>') => match self.peek() {
+                Some('=') => {
+                    self.advance();
+                    Some(Token::GreaterEqual)
+                }
+                _ => Some(Token::GreaterThan),
+            },
+            Some('=') => match self.peek() {
+                Some('=') => {
+                    self.advance();
+                    Some(Token::Equal)
+                }
+                _ => Some(Token::Assign),
+            },
+            Some('!') => match self.peek() {
+                Some('=') => {
+                    self.advance();
+                    Some(Token::NotEqual)
+                }
+                _ => Some(Token::Bang),
+            },
+            Some('/') => match self.peek() {
+                // Plain comments were skipped, this must be a doc comment.
+                Some('/') => Some(self.doc_comment()),
+                _ => Some(Token::Slash),
+            },
+            Some('"') => Some(self.string()),
+            Some(current_char) if current_char.is_digit(10) => Some(self.number(current_char)),
+            Some(current_char) if is_identifier_start(current_char) => {
+                // Todo: maybe it would be preferable to store a reference to a
+                // slice rather than storing a new heap allocated string.
+                let mut literal = String::new();
+                literal.push(current_char);
+
+                while let Some(next) = self.peek() {
+                    if !is_identifier_part(next) {
+                        break;
+                    }
+                    literal.push(next);
+                    self.advance();
+                }
+
+                match keyword(&literal) {
+                    Some(keyword) => Some(keyword),
+                    None => Some(Token::Ident(literal)),
+                }
+            }
+            Some(current_char) => Some(Token::Illegal(LexError::UnexpectedCharacter(
+                current_char,
+            ))),
+        }
+    }
+
+    /// Scan a string up to its closing quote, or up to the next `${` in
+    /// which case the segment is returned as an `Interpolation` and the
+    /// string resumes at the matching `}`.
+    fn string(&mut self) -> Token {
+        let mut literal = String::new();
+        // Keep going after a bad escape so the rest of the string doesn't
+        // get lexed as code, but remember the first problem.
+        let mut error = None;
+        loop {
+            match self.advance() {
+                Some('"') => break,
+                Some('$') if self.peek() == Some('{') => {
+                    self.advance();
+                    self.interpolations.push(0);
+                    return match error {
+                        Some(err) => Token::Illegal(err),
+                        None => Token::Interpolation(literal),
+                    };
+                }
+                Some('\\') => match self.escape() {
+                    Ok(c) => literal.push(c),
+                    Err(err) => if error.is_none() {
+                        error = Some(err)
+                    },
+                },
+                Some(current_char) => literal.push(current_char),
+                None => {
+                    // Any open interpolations went down with this string.
+                    self.interpolations.clear();
+                    return Token::Illegal(LexError::UnterminatedString);
+                }
+            }
+        }
+
+        match error {
+            Some(err) => Token::Illegal(err),
+            None => Token::LoxString(literal),
+        }
+    }
+
+    /// Decode an escape sequence, the backslash has already been consumed.
+    fn escape(&mut self) -> Result<char, LexError> {
+        match self.advance() {
+            Some('n') => Ok('\n'),
+            Some('t') => Ok('\t'),
+            Some('r') => Ok('\r'),
+            Some('0') => Ok('\0'),
+            Some('\\') => Ok('\\'),
+            Some('"') => Ok('"'),
+            Some('$') => Ok('$'),
+            Some('u') => {
+                let mut sequence = String::from("\\u");
+                if self.peek() == Some('{') {
+                    sequence.push('{');
+                    self.advance();
+                    while let Some(next) = self.peek() {
+                        if !next.is_digit(16) {
+                            break;
+                        }
+                        sequence.push(next);
+                        self.advance();
+                    }
+                    if self.peek() == Some('}') {
+                        sequence.push('}');
+                        self.advance();
+                        let hex = &sequence[3..sequence.len() - 1];
+                        if !hex.is_empty() && hex.len() <= 6 {
+                            if let Some(c) =
+                                u32::from_str_radix(hex, 16).ok().and_then(::std::char::from_u32)
+                            {
+                                return Ok(c);
+                            }
+                        }
+                    }
+                }
+                Err(LexError::InvalidUnicodeEscape(sequence))
+            }
+            Some(other) => Err(LexError::UnknownEscape(format!("\\{}", other))),
+            // Let the caller report the unterminated string.
+            None => Ok('\\'),
+        }
+    }
+
+    /// Decimal literals with an optional fraction and exponent, or `0x`,
+    /// `0b` and `0o` integers. Digits may be separated by `_`.
+    fn number(&mut self, first: char) -> Token {
+        let mut literal = String::new();
+        literal.push(first);
+
+        let radix = match (first, self.peek()) {
+            ('0', Some('x')) | ('0', Some('X')) => 16,
+            ('0', Some('b')) | ('0', Some('B')) => 2,
+            ('0', Some('o')) | ('0', Some('O')) => 8,
+            _ => 10,
+        };
+
+        let mut valid = if radix == 10 {
+            self.decimal(&mut literal)
+        } else {
+            literal.push(self.advance().unwrap());
+            self.digits(&mut literal, radix)
+        };
+
+        // Anything glued onto the end, like the second '.' in 1.2.3 or
+        // the letters in 12ab, makes the whole thing malformed.
+        valid &= match (self.peek(), self.peek_next()) {
+            (Some('.'), Some(next)) => !next.is_digit(10),
+            (Some(next), _) => !is_identifier_part(next),
+            _ => true,
+        };
+        if !valid {
+            while let Some(next) = self.peek() {
+                if !is_identifier_part(next) && next != '.' {
+                    break;
+                }
+                literal.push(next);
+                self.advance();
+            }
+            return Token::Illegal(LexError::MalformedNumber(literal));
+        }
+
+        let digits = literal.replace('_', "");
+        if radix == 10 {
+            Token::Number(digits.parse::<f64>().unwrap())
+        } else {
+            let value = digits[2..]
+                .chars()
+                .fold(0.0, |value, c| value * radix as f64 + c.to_digit(radix).unwrap() as f64);
+            Token::Number(value)
+        }
+    }
+
+    /// The rest of a decimal literal after its first digit.
+    fn decimal(&mut self, literal: &mut String) -> bool {
+        let mut valid = self.digits(literal, 10);
+
+        if let (Some('.'), Some(next)) = (self.peek(), self.peek_next()) {
+            if next.is_digit(10) {
+                literal.push('.');
+                self.advance();
+                valid &= self.digits(literal, 10);
+            }
+        }
+
+        if let (Some(e), Some(next)) = (self.peek(), self.peek_next()) {
+            if (e == 'e' || e == 'E') && (next.is_digit(10) || next == '+' || next == '-') {
+                literal.push(e);
+                self.advance();
+                if next == '+' || next == '-' {
+                    literal.push(next);
+                    self.advance();
+                }
+                valid &= self.digits(literal, 10);
+            }
+        }
+
+        valid
+    }
+
+    /// Consume digits in the given radix along with `_` separators. Returns
+    /// false unless it ends on a digit and every `_` follows one.
+    fn digits(&mut self, literal: &mut String, radix: u32) -> bool {
+        let mut valid = true;
+        let mut previous = literal.chars().last();
+        while let Some(next) = self.peek() {
+            if next == '_' {
+                valid &= previous.map_or(false, |c| c.is_digit(radix));
+            } else if !next.is_digit(radix) {
+                break;
+            }
+            literal.push(next);
+            self.advance();
+            previous = Some(next);
+        }
+        valid && previous.map_or(false, |c| c.is_digit(radix))
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        if self.index >= self.input.len() {
+            None
+        } else {
+            let current_char = self.input[self.index];
+            self.index += 1;
+            self.offset += current_char.len_utf8();
+            if current_char == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+            Some(current_char)
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        if self.index >= self.input.len() {
+            None
+        } else {
+            Some(self.input[self.index])
+        }
+    }
+
+    fn peek_next(&self) -> Option<char> {
+        if self.index + 1 >= self.input.len() {
+            None
+        } else {
+            Some(self.input[self.index + 1])
+        }
+    }
+}
+
+fn is_whitespace(c: char) -> bool {
+    c == ' ' || c == '\t' || c == '\r' || c == '\n'
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_identifier_part(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
@@ -1,63 +1,146 @@
-use lexer::Token;
+use lexer::{Span, Token};
 
 #[derive(Clone, Debug)]
 pub enum Expression {
     Assignment {
         id: usize,
-        name: Token,
+        span: Span,
+        name: Token<'static>,
         expr: Box<Expression>,
     },
     Binary {
         id: usize,
+        span: Span,
         l_expr: Box<Expression>,
-        operator: Token,
+        operator: Token<'static>,
         r_expr: Box<Expression>,
     },
+    /// `target op= value`, `operator` is the binary operator applied. The
+    /// target is a `Variable`, `Get` or `Index`, whose object and index
+    /// are only evaluated once.
+    CompoundAssignment {
+        id: usize,
+        span: Span,
+        target: Box<Expression>,
+        operator: Token<'static>,
+        value: Box<Expression>,
+    },
+    /// `a ?? b`, `b` is only evaluated when `a` is nil.
+    Coalesce {
+        id: usize,
+        span: Span,
+        l_expr: Box<Expression>,
+        r_expr: Box<Expression>,
+    },
+    /// `condition ? then_expr : else_expr`
+    Conditional {
+        id: usize,
+        span: Span,
+        condition: Box<Expression>,
+        then_expr: Box<Expression>,
+        else_expr: Box<Expression>,
+    },
     Call {
         id: usize,
+        span: Span,
         callee: Box<Expression>,
         arguments: Vec<Expression>,
+        /// `name: value` arguments, they always follow the positional ones.
+        named: Vec<(Token<'static>, Expression)>,
     },
+    /// `object.name`, or `object?.name` when `optional`, which makes the
+    /// rest of the chain nil if the object is nil.
     Get {
         id: usize,
+        span: Span,
         object: Box<Expression>,
-        name: Token,
+        name: Token<'static>,
+        optional: bool,
     },
     Grouping {
         id: usize,
+        span: Span,
         expr: Box<Expression>,
     },
+    /// A string with embedded expressions, `parts` alternate between
+    /// string literals and the interpolated expressions.
+    Interpolation {
+        id: usize,
+        span: Span,
+        parts: Vec<Expression>,
+    },
+    /// `object[index]`
+    Index {
+        id: usize,
+        span: Span,
+        object: Box<Expression>,
+        index: Box<Expression>,
+    },
+    /// An anonymous function, `fn (a) { ... }` or `(a) => ...`.
+    Lambda {
+        id: usize,
+        span: Span,
+        parameters: Vec<Parameter>,
+        body: Vec<Statement>,
+    },
+    /// A list literal, `[a, b, c]`.
+    List {
+        id: usize,
+        span: Span,
+        elements: Vec<Expression>,
+    },
     Literal {
         id: usize,
-        token: Token,
+        span: Span,
+        token: Token<'static>,
+    },
+    /// A map literal, `{key: value, ...}`, only in expression position.
+    Map {
+        id: usize,
+        span: Span,
+        entries: Vec<(Expression, Expression)>,
     },
     Logical {
         id: usize,
+        span: Span,
         l_expr: Box<Expression>,
-        operator: Token,
+        operator: Token<'static>,
         r_expr: Box<Expression>,
     },
     Set {
         id: usize,
-        name: Token,
+        span: Span,
+        name: Token<'static>,
         object: Box<Expression>,
         value: Box<Expression>,
     },
+    /// `object[index] = value`
+    SetIndex {
+        id: usize,
+        span: Span,
+        object: Box<Expression>,
+        index: Box<Expression>,
+        value: Box<Expression>,
+    },
     Super {
         id: usize,
-        method: Token,
+        span: Span,
+        method: Token<'static>,
     },
     This {
         id: usize,
+        span: Span,
     },
     Unary {
         id: usize,
-        operator: Token,
+        span: Span,
+        operator: Token<'static>,
         expr: Box<Expression>,
     },
     Variable {
         id: usize,
-        name: Token,
+        span: Span,
+        name: Token<'static>,
     },
 }
 
@@ -67,31 +150,121 @@ impl Expression {
             &Expression::Assignment { id, .. } => id,
             &Expression::Binary { id, .. } => id,
             &Expression::Call { id, .. } => id,
+            &Expression::Coalesce { id, .. } => id,
+            &Expression::Conditional { id, .. } => id,
+            &Expression::CompoundAssignment { id, .. } => id,
             &Expression::Get { id, .. } => id,
             &Expression::Grouping { id, .. } => id,
+            &Expression::Interpolation { id, .. } => id,
+            &Expression::Index { id, .. } => id,
+            &Expression::Lambda { id, .. } => id,
+            &Expression::List { id, .. } => id,
             &Expression::Literal { id, .. } => id,
             &Expression::Logical { id, .. } => id,
+            &Expression::Map { id, .. } => id,
             &Expression::Set { id, .. } => id,
+            &Expression::SetIndex { id, .. } => id,
             &Expression::Super { id, .. } => id,
             &Expression::This { id, .. } => id,
             &Expression::Unary { id, .. } => id,
             &Expression::Variable { id, .. } => id,
         }
     }
+
+    pub fn get_span(&self) -> Span {
+        match self {
+            &Expression::Assignment { span, .. } => span,
+            &Expression::Binary { span, .. } => span,
+            &Expression::Call { span, .. } => span,
+            &Expression::Coalesce { span, .. } => span,
+            &Expression::Conditional { span, .. } => span,
+            &Expression::CompoundAssignment { span, .. } => span,
+            &Expression::Get { span, .. } => span,
+            &Expression::Grouping { span, .. } => span,
+            &Expression::Interpolation { span, .. } => span,
+            &Expression::Index { span, .. } => span,
+            &Expression::Lambda { span, .. } => span,
+            &Expression::List { span, .. } => span,
+            &Expression::Literal { span, .. } => span,
+            &Expression::Logical { span, .. } => span,
+            &Expression::Map { span, .. } => span,
+            &Expression::Set { span, .. } => span,
+            &Expression::SetIndex { span, .. } => span,
+            &Expression::Super { span, .. } => span,
+            &Expression::This { span, .. } => span,
+            &Expression::Unary { span, .. } => span,
+            &Expression::Variable { span, .. } => span,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Parameter {
+    pub name: Token<'static>,
+    /// `...name`, collects the remaining arguments into a list. Only the
+    /// last parameter can be one.
+    pub rest: bool,
+    /// Evaluated on each call that doesn't pass the parameter, after the
+    /// parameters before it are bound.
+    pub default: Option<Expression>,
 }
 
 #[derive(Clone, Debug)]
 pub enum Statement {
     Class {
-        name: Token,
+        name: Token<'static>,
         super_class: Option<Expression>,
         methods: Vec<Statement>,
+        doc: Option<String>,
+        span: Span,
     },
-    Block(Vec<Statement>),
-    Expression(Expression),
-    FunctionDeclaration(Token, Vec<Token>, Vec<Statement>),
-    If(Expression, Box<Statement>, Option<Box<Statement>>),
-    Return(Option<Expression>),
-    VariableDeclaration(Token, Option<Expression>),
-    While(Expression, Box<Statement>),
+    Block(Vec<Statement>, Span),
+    Break(Span),
+    Continue(Span),
+    Expression(Expression, Span),
+    FunctionDeclaration(Token<'static>, Vec<Parameter>, Vec<Statement>, Option<String>, Span),
+    If(Expression, Box<Statement>, Option<Box<Statement>>, Span),
+    Return(Option<Expression>, Span),
+    Throw(Expression, Span),
+    /// At least one of `catch`, which names the caught value, and `finally`
+    /// is present.
+    Try {
+        body: Vec<Statement>,
+        catch: Option<(Token<'static>, Vec<Statement>)>,
+        finally: Option<Vec<Statement>>,
+        span: Span,
+    },
+    VariableDeclaration(Token<'static>, Option<Expression>, Option<String>, Span),
+    /// The optional expression is a `for` loop's increment, evaluated after
+    /// the body, including when the body `continue`s.
+    While(Expression, Box<Statement>, Option<Expression>, Span),
+}
+
+impl Statement {
+    pub fn get_span(&self) -> Span {
+        match self {
+            &Statement::Class { span, .. } => span,
+            &Statement::Block(_, span) => span,
+            &Statement::Break(span) => span,
+            &Statement::Continue(span) => span,
+            &Statement::Expression(_, span) => span,
+            &Statement::FunctionDeclaration(_, _, _, _, span) => span,
+            &Statement::If(_, _, _, span) => span,
+            &Statement::Return(_, span) => span,
+            &Statement::Throw(_, span) => span,
+            &Statement::Try { span, .. } => span,
+            &Statement::VariableDeclaration(_, _, _, span) => span,
+            &Statement::While(_, _, _, span) => span,
+        }
+    }
+
+    /// The `///` comment preceding a declaration, if any.
+    pub fn doc(&self) -> Option<&str> {
+        match self {
+            &Statement::Class { ref doc, .. }
+            | &Statement::FunctionDeclaration(_, _, _, ref doc, _)
+            | &Statement::VariableDeclaration(_, _, ref doc, _) => doc.as_ref().map(|d| d.as_str()),
+            _ => None,
+        }
+    }
 }
@@ -1,4 +1,4 @@
-pub use self::core::{Expression, Statement};
+pub use self::core::{Expression, Parameter, Statement};
 pub use self::visitor::{MutVisitor, Visitor};
 
 mod core;
@@ -28,23 +28,100 @@ impl<'a> Visitor for ASTStringVisitor<'a> {
             Expression::Call {
                 ref callee,
                 ref arguments,
+                ref named,
                 ..
             } => format!(
-                "(Call {} {})",
+                "(Call {} {}{})",
                 self.visit_expression(callee),
                 arguments
                     .iter()
                     .map(|e| self.visit_expression(e))
+                    .collect::<String>(),
+                named
+                    .iter()
+                    .map(|&(ref name, ref e)| format!("({:?}: {})", name, self.visit_expression(e)))
                     .collect::<String>()
             ),
+            Expression::CompoundAssignment {
+                ref target,
+                ref operator,
+                ref value,
+                ..
+            } => format!(
+                "(CompoundAssignment {:?} {} {})",
+                operator,
+                self.visit_expression(target),
+                self.visit_expression(value)
+            ),
+            Expression::Coalesce {
+                ref l_expr,
+                ref r_expr,
+                ..
+            } => format!(
+                "(Coalesce {} {})",
+                self.visit_expression(l_expr),
+                self.visit_expression(r_expr)
+            ),
+            Expression::Conditional {
+                ref condition,
+                ref then_expr,
+                ref else_expr,
+                ..
+            } => format!(
+                "(Conditional {} {} {})",
+                self.visit_expression(condition),
+                self.visit_expression(then_expr),
+                self.visit_expression(else_expr)
+            ),
             Expression::Get {
                 ref name,
                 ref object,
+                optional: false,
                 ..
             } => format!("(Get {:?} {})", name, self.visit_expression(object)),
+            Expression::Get {
+                ref name,
+                ref object,
+                optional: true,
+                ..
+            } => format!("(OptionalGet {:?} {})", name, self.visit_expression(object)),
             Expression::Grouping { ref expr, .. } => {
                 format!("(Grouping {})", self.visit_expression(expr))
             }
+            Expression::Interpolation { ref parts, .. } => format!(
+                "(Interpolation {})",
+                parts
+                    .iter()
+                    .map(|e| self.visit_expression(e))
+                    .collect::<String>()
+            ),
+            Expression::Index {
+                ref object,
+                ref index,
+                ..
+            } => format!(
+                "(Index {} {})",
+                self.visit_expression(object),
+                self.visit_expression(index)
+            ),
+            Expression::Lambda {
+                ref parameters,
+                ref body,
+                ..
+            } => format!(
+                "(Lambda [{}] {})",
+                self.print_parameters(parameters),
+                body.iter()
+                    .map(|s| self.visit_statement(s))
+                    .collect::<String>()
+            ),
+            Expression::List { ref elements, .. } => format!(
+                "(List {})",
+                elements
+                    .iter()
+                    .map(|e| self.visit_expression(e))
+                    .collect::<String>()
+            ),
             Expression::Literal { ref token, .. } => format!("(Literal {:?})", token),
             Expression::Logical {
                 ref l_expr,
@@ -57,6 +134,17 @@ impl<'a> Visitor for ASTStringVisitor<'a> {
                 self.visit_expression(l_expr),
                 self.visit_expression(r_expr)
             ),
+            Expression::Map { ref entries, .. } => format!(
+                "(Map {})",
+                entries
+                    .iter()
+                    .map(|&(ref key, ref value)| format!(
+                        "({} {})",
+                        self.visit_expression(key),
+                        self.visit_expression(value)
+                    ))
+                    .collect::<String>()
+            ),
             Expression::Set {
                 ref name,
                 ref object,
@@ -68,6 +156,17 @@ impl<'a> Visitor for ASTStringVisitor<'a> {
                 self.visit_expression(object),
                 self.visit_expression(value)
             ),
+            Expression::SetIndex {
+                ref object,
+                ref index,
+                ref value,
+                ..
+            } => format!(
+                "(SetIndex \n\t object: {} \n\t index: {} \n\t value {})",
+                self.visit_expression(object),
+                self.visit_expression(index),
+                self.visit_expression(value)
+            ),
             Expression::Super { ref method, .. } => format!("(super.{:?})", method),
             Expression::This { .. } => String::from("(this)"),
             Expression::Unary {
@@ -81,7 +180,7 @@ impl<'a> Visitor for ASTStringVisitor<'a> {
 
     fn visit_statement(&self, s: &Statement) -> String {
         match *s {
-            Statement::Class{ref name, ref methods, ref super_class} => format!(
+            Statement::Class{ref name, ref methods, ref super_class, ..} => format!(
                 "(ClassDeclaration Statement \n\tname: {:?} \n\tsuperclass: {} \n\tmethods: [{}])",
                 name,
                 match super_class {
@@ -94,29 +193,27 @@ impl<'a> Visitor for ASTStringVisitor<'a> {
                     .join(", "),
             ),
 
-            Statement::Block(ref statements) => format!(
+            Statement::Block(ref statements, _) => format!(
                 "(Block Statement {})",
                 statements
                     .iter()
                     .map(|s| self.visit_statement(s))
                     .collect::<String>()
             ),
-            Statement::Expression(ref expr) => {
+            Statement::Expression(ref expr, _) => {
                 format!("(Expression Statement {})", self.visit_expression(expr))
             }
-            Statement::FunctionDeclaration(ref name, ref parameters, ref body) => format!(
+            Statement::FunctionDeclaration(ref name, ref parameters, ref body, _, _) => format!(
                 "(FunctionDeclaration Statement \n\tname: {:?} \n\tparameters: [{}] \n\tbody: {} \n)",
                 name,
-                parameters
-                    .iter()
-                    .map(|t| format!("{:?}", t))
-                    .collect::<Vec<_>>()
-                    .join(", "),
+                self.print_parameters(parameters),
                 body.iter()
                     .map(|s| self.visit_statement(s))
                     .collect::<String>()
             ),
-            Statement::If(ref conditional, ref then_stmt, ref else_stmt) => format!(
+            Statement::Break(_) => String::from("(Break Statement)"),
+            Statement::Continue(_) => String::from("(Continue Statement)"),
+            Statement::If(ref conditional, ref then_stmt, ref else_stmt, _) => format!(
                 "(If Statement {} {} {})",
                 self.visit_expression(conditional),
                 self.visit_statement(then_stmt),
@@ -125,13 +222,48 @@ impl<'a> Visitor for ASTStringVisitor<'a> {
                     &None => String::from(""),
                 }
             ),
-            Statement::Return(ref expr_option) => format!("(Return Statement {})",
+            Statement::Return(ref expr_option, _) => format!("(Return Statement {})",
                 match expr_option {
                     &Some(ref expr) => self.visit_expression(expr),
                     &None => "nil".to_string(),
                 }
             ),
-            Statement::VariableDeclaration(ref token, ref expr_option) => format!(
+            Statement::Throw(ref expr, _) => {
+                format!("(Throw Statement {})", self.visit_expression(expr))
+            }
+            Statement::Try {
+                ref body,
+                ref catch,
+                ref finally,
+                ..
+            } => format!(
+                "(Try Statement {} {} {})",
+                body.iter()
+                    .map(|s| self.visit_statement(s))
+                    .collect::<String>(),
+                match catch {
+                    &Some((ref name, ref handler)) => format!(
+                        "(Catch {:?} {})",
+                        name,
+                        handler
+                            .iter()
+                            .map(|s| self.visit_statement(s))
+                            .collect::<String>()
+                    ),
+                    &None => String::from(""),
+                },
+                match finally {
+                    &Some(ref finally) => format!(
+                        "(Finally {})",
+                        finally
+                            .iter()
+                            .map(|s| self.visit_statement(s))
+                            .collect::<String>()
+                    ),
+                    &None => String::from(""),
+                }
+            ),
+            Statement::VariableDeclaration(ref token, ref expr_option, _, _) => format!(
                 "(VariableDeclaration Statement {:?} {})",
                 token,
                 match expr_option {
@@ -139,15 +271,33 @@ impl<'a> Visitor for ASTStringVisitor<'a> {
                     &None => "nil".to_string(),
                 }
             ),
-            Statement::While(ref expr, ref stmt) => format!(
-                "(While Statement {} {})",
+            Statement::While(ref expr, ref stmt, ref increment, _) => format!(
+                "(While Statement {} {} {})",
                 self.visit_expression(expr),
-                self.visit_statement(stmt)
+                self.visit_statement(stmt),
+                match increment {
+                    &Some(ref increment) => self.visit_expression(increment),
+                    &None => String::from(""),
+                }
             ),
         }
     }
 }
 
+impl<'a> ASTStringVisitor<'a> {
+    fn print_parameters(&self, parameters: &[Parameter]) -> String {
+        parameters
+            .iter()
+            .map(|p| match p.default {
+                _ if p.rest => format!("...{:?}", p.name),
+                Some(ref default) => format!("{:?} = {}", p.name, self.visit_expression(default)),
+                None => format!("{:?}", p.name),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 impl<'a> fmt::Display for ASTStringVisitor<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for s in self.statements {
@@ -0,0 +1,357 @@
+//! Every diagnostic catlox can report, keyed by a stable code.
+//!
+//! Codes are grouped by phase: E01xx lexer, E02xx parser, E03xx resolver,
+//! E04xx interpreter. Codes are never reused once published, retired
+//! entries are removed rather than renumbered.
+//!
+//! Each entry has a neutral message and a "spicy" one preserving the
+//! original tone of the project, the spicy set is opt-in via `set_spicy`.
+//! Messages are templates, each `{}` is replaced by the next argument.
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static SPICY: AtomicBool = AtomicBool::new(false);
+
+/// Choose between the neutral (default) and spicy message sets.
+pub fn set_spicy(spicy: bool) {
+    SPICY.store(spicy, Ordering::Relaxed);
+}
+
+pub fn is_spicy() -> bool {
+    SPICY.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ErrorCode {
+    // Lexer
+    UnterminatedString,
+    UnexpectedCharacter,
+    MalformedNumber,
+    UnknownEscape,
+    InvalidUnicodeEscape,
+    UnterminatedComment,
+    // Parser
+    UnexpectedToken,
+    MissingSemicolon,
+    UnclosedBlock,
+    // E0204 and E0205, the parameter and argument limits, are retired.
+    InvalidAssignmentTarget,
+    ExpectedExpression,
+    RestParameterNotLast,
+    PositionalAfterNamed,
+    // Resolver
+    AlreadyDeclared,
+    SuperOutsideClass,
+    SuperWithoutSuperclass,
+    ThisOutsideClass,
+    ReadInOwnInitializer,
+    ReturnFromTopLevel,
+    ReturnFromInitializer,
+    BreakOutsideLoop,
+    ContinueOutsideLoop,
+    // Interpreter
+    ArityMismatch,
+    DivisionByZero,
+    InvalidOperands,
+    InvalidOperand,
+    NotCallable,
+    UndefinedProperty,
+    PropertyOnNonInstance,
+    FieldOnNonInstance,
+    UndefinedVariable,
+    AssignToUndefined,
+    SuperclassNotClass,
+    ReturnOutsideFunction,
+    IndexOutOfBounds,
+    InvalidIndex,
+    NotIndexable,
+    InvalidMapKey,
+    KeyNotFound,
+    MissingArgument,
+    UnknownParameter,
+    DuplicateArgument,
+    UncaughtException,
+    InvalidNumberOperator,
+    InvalidNilOperator,
+    InvalidBooleanOperator,
+    InvalidLiteral,
+    Internal,
+}
+
+impl ErrorCode {
+    /// The stable identifier, e.g. `E0402`.
+    pub fn as_str(&self) -> &'static str {
+        self.entry().0
+    }
+
+    /// The message for this code in the active message set.
+    pub fn message(&self, args: &[&str]) -> String {
+        let (_, neutral, spicy) = self.entry();
+        let mut message = String::new();
+        let mut args = args.iter();
+        let template = if is_spicy() { spicy } else { neutral };
+        for (i, piece) in template.split("{}").enumerate() {
+            if i > 0 {
+                if let Some(arg) = args.next() {
+                    message.push_str(arg);
+                }
+            }
+            message.push_str(piece);
+        }
+        message
+    }
+
+    /// (code, neutral message, spicy message)
+    fn entry(&self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            &ErrorCode::UnterminatedString => (
+                "E0101",
+                "Unterminated string literal",
+                "Finish your fucking string!",
+            ),
+            &ErrorCode::UnexpectedCharacter => (
+                "E0102",
+                "Unexpected character '{}'",
+                "What the fuck is '{}' supposed to be?",
+            ),
+            &ErrorCode::MalformedNumber => (
+                "E0103",
+                "Malformed number literal '{}'",
+                "'{}' is not a fucking number",
+            ),
+            &ErrorCode::UnknownEscape => (
+                "E0104",
+                "Unknown escape sequence '{}'",
+                "'{}' isn't a fucking escape sequence",
+            ),
+            &ErrorCode::InvalidUnicodeEscape => (
+                "E0105",
+                "Invalid unicode escape '{}', expected \\u{XXXX}",
+                "'{}' is garbage, unicode escapes look like \\u{XXXX}",
+            ),
+            &ErrorCode::UnterminatedComment => (
+                "E0106",
+                "Unterminated block comment",
+                "Close your fucking comment!",
+            ),
+            &ErrorCode::UnexpectedToken => (
+                "E0201",
+                "Expected {}, found {}",
+                "What the fuck is this shit! Expected {}, found {}",
+            ),
+            &ErrorCode::MissingSemicolon => (
+                "E0202",
+                "Expected ';' after statement, found {}",
+                "There should be a fucking semicolon after this expression! Found {}",
+            ),
+            &ErrorCode::UnclosedBlock => (
+                "E0203",
+                "Unclosed block opened at {}",
+                "You didn't close your fucking block! The one at {}",
+            ),
+            &ErrorCode::InvalidAssignmentTarget => (
+                "E0206",
+                "Invalid assignment target",
+                "Are you trying to assign something? Get it the fuck right!",
+            ),
+            &ErrorCode::ExpectedExpression => (
+                "E0207",
+                "Expected an expression, found {}",
+                "There should be some shit here! Not {}",
+            ),
+            &ErrorCode::RestParameterNotLast => (
+                "E0208",
+                "A rest parameter must be the last parameter",
+                "Nothing goes after the rest parameter, that's the whole fucking point",
+            ),
+            &ErrorCode::PositionalAfterNamed => (
+                "E0209",
+                "Positional arguments must come before named arguments",
+                "Positional arguments go first, named ones after. It's not fucking hard",
+            ),
+            &ErrorCode::AlreadyDeclared => (
+                "E0301",
+                "A variable named '{}' is already declared in this scope",
+                "{} is already declared in this scope, pick another fucking name",
+            ),
+            &ErrorCode::SuperOutsideClass => (
+                "E0302",
+                "Cannot use 'super' outside of a class",
+                "Cannot use 'super' outside of a class.",
+            ),
+            &ErrorCode::SuperWithoutSuperclass => (
+                "E0303",
+                "Cannot use 'super' in a class with no superclass",
+                "Cannot use 'super' in a class with no superclass.",
+            ),
+            &ErrorCode::ThisOutsideClass => (
+                "E0304",
+                "Cannot use 'this' outside of a class",
+                "You can't use this outside of a class!",
+            ),
+            &ErrorCode::ReadInOwnInitializer => (
+                "E0305",
+                "Cannot read local variable '{}' in its own initializer",
+                "Cannot read local variable {} in its own initializer.",
+            ),
+            &ErrorCode::ReturnFromTopLevel => (
+                "E0306",
+                "Cannot return from top-level code",
+                "Cannot return from top level code",
+            ),
+            &ErrorCode::ReturnFromInitializer => (
+                "E0307",
+                "Cannot return a value from an initializer",
+                "Initializers hand back 'this', stop trying to return shit from them",
+            ),
+            &ErrorCode::BreakOutsideLoop => (
+                "E0308",
+                "Cannot use 'break' outside of a loop",
+                "Break out of what? There's no fucking loop here!",
+            ),
+            &ErrorCode::ContinueOutsideLoop => (
+                "E0309",
+                "Cannot use 'continue' outside of a loop",
+                "Continue what? There's no fucking loop here!",
+            ),
+            &ErrorCode::ArityMismatch => (
+                "E0401",
+                "Expected {} arguments but got {}",
+                "This wants {} arguments and you passed it {}, try again dipshit",
+            ),
+            &ErrorCode::DivisionByZero => (
+                "E0402",
+                "Division by zero",
+                "No cabrón, I will not divide by zero!",
+            ),
+            &ErrorCode::InvalidOperands => (
+                "E0403",
+                "Unsupported operand types for '{}': {} and {}",
+                "NO! NO! NO! You can't {} a {} and a {}!",
+            ),
+            &ErrorCode::InvalidOperand => (
+                "E0404",
+                "Unsupported operand type for '{}': {}",
+                "🖕🖕🖕🖕 ({} on a {})",
+            ),
+            &ErrorCode::NotCallable => (
+                "E0405",
+                "Can only call functions and classes, not {}",
+                "You can't call this shit! It's a {}",
+            ),
+            &ErrorCode::UndefinedProperty => (
+                "E0406",
+                "Undefined property '{}'",
+                "{} is a fucking undefined property!",
+            ),
+            &ErrorCode::PropertyOnNonInstance => (
+                "E0407",
+                "Only instances have properties, not {}",
+                "Only instances have properties asshole! Not {}",
+            ),
+            &ErrorCode::FieldOnNonInstance => (
+                "E0408",
+                "Only instances have fields, not {}",
+                "Only instances have fields dumbass! Not {}",
+            ),
+            &ErrorCode::UndefinedVariable => (
+                "E0409",
+                "Undefined variable '{}'",
+                "{} is super fucking undefined",
+            ),
+            &ErrorCode::AssignToUndefined => (
+                "E0410",
+                "Cannot assign to undefined variable '{}'",
+                "{} is undefined why would you try to assign \
+                 to something that hasn't been defined?!?!",
+            ),
+            &ErrorCode::SuperclassNotClass => (
+                "E0411",
+                "Superclass must be a class",
+                "Superclass must be a class!",
+            ),
+            &ErrorCode::ReturnOutsideFunction => (
+                "E0412",
+                "Return can only be used inside a function",
+                "Return can only be used in function scope dummy",
+            ),
+            &ErrorCode::IndexOutOfBounds => (
+                "E0413",
+                "Index {} is out of bounds for a list of length {}",
+                "There's no index {} you muppet, the list only has {} things in it",
+            ),
+            &ErrorCode::InvalidIndex => (
+                "E0414",
+                "List indices must be whole numbers, not {}",
+                "What the hell kind of index is {}? Use a whole number!",
+            ),
+            &ErrorCode::NotIndexable => (
+                "E0415",
+                "Only lists and maps can be indexed, not {}",
+                "You can't index into a fucking {}!",
+            ),
+            &ErrorCode::InvalidMapKey => (
+                "E0416",
+                "Map keys must be strings, numbers or booleans, not {}",
+                "A {} as a map key? Strings, numbers or booleans, genius",
+            ),
+            &ErrorCode::KeyNotFound => (
+                "E0417",
+                "Key {} not found in map",
+                "There's no fucking {} in this map",
+            ),
+            &ErrorCode::MissingArgument => (
+                "E0418",
+                "Missing argument for parameter '{}'",
+                "You forgot to pass '{}', genius",
+            ),
+            &ErrorCode::UnknownParameter => (
+                "E0419",
+                "{} has no parameter named '{}'",
+                "{} has no fucking parameter called '{}'",
+            ),
+            &ErrorCode::DuplicateArgument => (
+                "E0420",
+                "Parameter '{}' was given more than one argument",
+                "Make up your mind, '{}' got passed more than once",
+            ),
+            &ErrorCode::UncaughtException => (
+                "E0421",
+                "Uncaught exception: {}",
+                "Nobody caught {}, now it's all over the floor",
+            ),
+            &ErrorCode::InvalidNumberOperator => (
+                "E0422",
+                "Unsupported operator '{}' for numbers",
+                "NO! NO you can't do that! Fuuuuuuck!",
+            ),
+            &ErrorCode::InvalidNilOperator => (
+                "E0423",
+                "Unsupported operator '{}' for nil",
+                "Fuck no you asshole I'm not doing that shit!",
+            ),
+            &ErrorCode::InvalidBooleanOperator => (
+                "E0424",
+                "Unsupported operator '{}' for booleans",
+                "¡Chinga tu madre!",
+            ),
+            &ErrorCode::InvalidLiteral => (
+                "E0425",
+                "{} is not a literal",
+                "🐑💨",
+            ),
+            &ErrorCode::Internal => (
+                "E0499",
+                "Internal interpreter error: {}",
+                "Internal interpreter error: shit is fucked, {}",
+            ),
+        }
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.as_str())
+    }
+}
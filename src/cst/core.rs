@@ -0,0 +1,75 @@
+use lexer::{Lexer, SpannedToken};
+use parser::{Event, Parser};
+use super::tree::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+
+/// Build a lossless syntax tree, printing it gives back `source` unchanged.
+///
+/// The shape comes from the parser itself: it runs over the tokens and
+/// its events are replayed here with the trivia filled back in. Trivia is
+/// attached to the innermost node of the token following it, so doc
+/// comments end up in their declaration. Building never fails, a
+/// declaration that doesn't parse goes in an `Error` node and is reported
+/// when the tree is lowered.
+pub fn parse(source: &str) -> SyntaxNode {
+    let tokens: Vec<SpannedToken> = Lexer::lossless(source).collect();
+    let events = Parser::new(&tokens, 0).events();
+
+    let mut builder = Builder {
+        tokens: &tokens,
+        source,
+        index: 0,
+        stack: vec![SyntaxNode::new(SyntaxKind::Root)],
+    };
+    for event in events {
+        match event {
+            Event::Start(kind) => builder.stack.push(SyntaxNode::new(kind)),
+            Event::Token => builder.bump(),
+            Event::Finish => {
+                let node = builder.stack.pop().unwrap();
+                builder.push(SyntaxElement::Node(node));
+            }
+        }
+    }
+    builder.trivia();
+    builder.stack.pop().unwrap()
+}
+
+struct Builder<'a> {
+    tokens: &'a [SpannedToken<'a>],
+    source: &'a str,
+    index: usize,
+    /// The nodes started but not yet finished, the root at the bottom.
+    stack: Vec<SyntaxNode>,
+}
+
+impl<'a> Builder<'a> {
+    fn push(&mut self, element: SyntaxElement) {
+        self.stack.last_mut().unwrap().children.push(element);
+    }
+
+    /// Move any trivia under the cursor into the current node.
+    fn trivia(&mut self) {
+        while self.index < self.tokens.len() && self.tokens[self.index].token.is_trivia() {
+            self.push_token();
+        }
+    }
+
+    /// Move the next token the parser consumed, and the trivia before it,
+    /// into the current node.
+    fn bump(&mut self) {
+        self.trivia();
+        if self.index < self.tokens.len() {
+            self.push_token();
+        }
+    }
+
+    fn push_token(&mut self) {
+        let t = &self.tokens[self.index];
+        self.push(SyntaxElement::Token(SyntaxToken {
+            token: t.token.clone().into_owned(),
+            span: t.span,
+            text: self.source[t.span.start..t.span.end].to_string(),
+        }));
+        self.index += 1;
+    }
+}
@@ -0,0 +1,5 @@
+pub use self::core::parse;
+pub use self::tree::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+
+mod core;
+mod tree;
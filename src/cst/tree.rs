@@ -0,0 +1,131 @@
+use ast::Statement;
+use lexer::{Span, SpannedToken, Token};
+use parser::{ParseError, Parser};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The kinds of node the parser marks out, statements first and then
+/// expressions. Nodes nest as the grammar does.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SyntaxKind {
+    Root,
+    Block,
+    Break,
+    Class,
+    Continue,
+    ExpressionStatement,
+    For,
+    Function,
+    If,
+    Let,
+    Parameter,
+    Return,
+    Throw,
+    Try,
+    While,
+    Assignment,
+    Binary,
+    Call,
+    Coalesce,
+    CompoundAssignment,
+    Conditional,
+    Get,
+    Grouping,
+    Index,
+    Interpolation,
+    Lambda,
+    List,
+    Literal,
+    Logical,
+    Map,
+    MapEntry,
+    NamedArgument,
+    Super,
+    This,
+    Unary,
+    Variable,
+    /// A declaration that failed to parse, along with the tokens skipped
+    /// to recover from it.
+    Error,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SyntaxToken {
+    pub token: Token<'static>,
+    pub span: Span,
+    /// The exact source text, including delimiters and escapes.
+    pub text: String,
+}
+
+impl SyntaxToken {
+    pub fn is_trivia(&self) -> bool {
+        self.token.is_trivia()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SyntaxNode {
+    pub kind: SyntaxKind,
+    pub children: Vec<SyntaxElement>,
+}
+
+impl SyntaxNode {
+    pub fn new(kind: SyntaxKind) -> SyntaxNode {
+        SyntaxNode {
+            kind,
+            children: vec![],
+        }
+    }
+
+    /// All tokens under this node in source order, trivia included.
+    pub fn tokens(&self) -> Vec<&SyntaxToken> {
+        let mut tokens = vec![];
+        for child in &self.children {
+            match child {
+                &SyntaxElement::Node(ref node) => tokens.extend(node.tokens()),
+                &SyntaxElement::Token(ref token) => tokens.push(token),
+            }
+        }
+        tokens
+    }
+
+    /// The span from the first to the last token under this node.
+    pub fn span(&self) -> Option<Span> {
+        let tokens = self.tokens();
+        match (tokens.first(), tokens.last()) {
+            (Some(first), Some(last)) => Some(first.span.to(last.span)),
+            _ => None,
+        }
+    }
+
+    /// Lower to the abstract syntax tree the resolver and interpreter use.
+    ///
+    /// This is a second parse: the tree's tokens, trivia included, are run
+    /// through the `Parser` again rather than the nodes being walked, so the
+    /// result and any errors are exactly what parsing the source gives.
+    pub fn lower(&self, parse_seed: usize) -> Result<(usize, Vec<Statement>), Vec<ParseError>> {
+        let tokens: Vec<SpannedToken> = self.tokens()
+            .into_iter()
+            .map(|t| SpannedToken {
+                token: t.token.clone(),
+                span: t.span,
+            })
+            .collect();
+        Parser::new(&tokens, parse_seed).parse()
+    }
+}
+
+/// Writes back the exact source the tree was built from.
+impl Display for SyntaxNode {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        for token in self.tokens() {
+            write!(f, "{}", token.text)?;
+        }
+        Ok(())
+    }
+}
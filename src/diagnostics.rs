@@ -0,0 +1,188 @@
+//! Rendering of parse, resolver and runtime errors against the source they
+//! came from, in the style of:
+//!
+//! ```text
+//! error[E0202]: Expected ';' after statement, found 'fn'
+//!  --> fixtures/fibonacci.cbox:4:1
+//!   |
+//! 4 | fn r_fib(n) {
+//!   | ^^
+//!   = help: statements end with a semicolon
+//! ```
+
+use catalog::ErrorCode;
+use interpreter::RuntimeError;
+use lexer::{Span, Token};
+use parser::ParseError;
+use resolver::ResolverError;
+
+const RED: &str = "\x1b[1;31m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    pub code: Option<ErrorCode>,
+    pub message: String,
+    pub span: Option<Span>,
+    pub notes: Vec<String>,
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(code: Option<ErrorCode>, message: String, span: Option<Span>) -> Diagnostic {
+        Diagnostic {
+            code,
+            message,
+            span,
+            notes: vec![],
+            help: None,
+        }
+    }
+}
+
+impl<'a> From<&'a ParseError> for Diagnostic {
+    fn from(err: &'a ParseError) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new(Some(err.code()), err.to_string(), Some(err.span()));
+        match err {
+            &ParseError::MissingSemicolon { .. } => {
+                diagnostic.help = Some(String::from("statements end with a semicolon"));
+            }
+            &ParseError::UnclosedBlock { .. } => {
+                diagnostic.help = Some(format!("add a closing '{}'", Token::RightBrace));
+            }
+            &ParseError::InvalidAssignmentTarget { .. } => {
+                diagnostic.help = Some(String::from(
+                    "only variables, fields and list/map elements can be assigned to",
+                ));
+            }
+            _ => (),
+        }
+        diagnostic
+    }
+}
+
+impl<'a> From<&'a ResolverError> for Diagnostic {
+    fn from(err: &'a ResolverError) -> Diagnostic {
+        Diagnostic::new(Some(err.code), err.message.clone(), Some(err.span))
+    }
+}
+
+impl<'a> From<&'a RuntimeError> for Diagnostic {
+    fn from(err: &'a RuntimeError) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new(Some(err.code), err.message.clone(), err.span);
+        diagnostic.notes = err.stack.iter().map(|frame| frame.to_string()).collect();
+        diagnostic
+    }
+}
+
+/// Renders diagnostics for one source file.
+pub struct Renderer<'a> {
+    file_name: &'a str,
+    source: &'a str,
+    colour: bool,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(file_name: &'a str, source: &'a str, colour: bool) -> Renderer<'a> {
+        Renderer {
+            file_name,
+            source,
+            colour,
+        }
+    }
+
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let severity = match diagnostic.code {
+            Some(code) => format!("error[{}]", code),
+            None => String::from("error"),
+        };
+        let mut out = format!(
+            "{}: {}\n",
+            self.paint(RED, &severity),
+            self.paint(BOLD, &diagnostic.message)
+        );
+
+        let gutter_width = match diagnostic.span {
+            Some(span) => span.line.to_string().len(),
+            None => 1,
+        };
+        let gutter = " ".repeat(gutter_width);
+
+        if let Some(span) = diagnostic.span {
+            out.push_str(&format!(
+                "{}{} {}:{}:{}\n",
+                gutter,
+                self.paint(BLUE, "-->"),
+                self.file_name,
+                span.line,
+                span.column
+            ));
+
+            if let Some(line) = self.source.lines().nth(span.line - 1) {
+                let bar = self.paint(BLUE, "|");
+                out.push_str(&format!("{} {}\n", gutter, bar));
+                out.push_str(&format!(
+                    "{} {} {}\n",
+                    self.paint(BLUE, &span.line.to_string()),
+                    bar,
+                    line
+                ));
+                out.push_str(&format!(
+                    "{} {} {}{}\n",
+                    gutter,
+                    bar,
+                    padding(line, span.column),
+                    self.paint(RED, &"^".repeat(underline_width(line, span)))
+                ));
+            }
+        }
+
+        for note in &diagnostic.notes {
+            out.push_str(&format!("{} {} note: {}\n", gutter, self.paint(BLUE, "="), note));
+        }
+        if let Some(ref help) = diagnostic.help {
+            out.push_str(&format!("{} {} help: {}\n", gutter, self.paint(BLUE, "="), help));
+        }
+
+        out
+    }
+
+    fn paint(&self, colour: &str, text: &str) -> String {
+        if self.colour {
+            format!("{}{}{}", colour, text, RESET)
+        } else {
+            String::from(text)
+        }
+    }
+}
+
+/// Blank out `line` before `column`, keeping its tabs so the caret lines up
+/// under the echoed line however wide the terminal draws them.
+fn padding(line: &str, column: usize) -> String {
+    line.chars()
+        .take(column - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect()
+}
+
+/// How many characters of `line` the span covers, at least one so that
+/// empty spans (such as the end of the input) still get a caret.
+fn underline_width(line: &str, span: Span) -> usize {
+    let remaining = line.chars().count().saturating_sub(span.column - 1);
+    let covered = line.chars()
+        .skip(span.column - 1)
+        .scan(0, |bytes, c| {
+            *bytes += c.len_utf8();
+            Some(*bytes)
+        })
+        .take_while(|bytes| *bytes <= span.end - span.start)
+        .count();
+
+    if covered == 0 || remaining == 0 {
+        1
+    } else {
+        covered
+    }
+}
@@ -1,16 +1,17 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::fmt::{Display, Formatter, Result as FmtResult};
-use super::core::{Callable, Interpreter, Types};
+use super::core::{Arity, Callable, Interpreter, Types};
+use super::error::RuntimeError;
 
 #[derive(Debug)]
 pub struct Clock {}
 
 impl Callable for Clock {
-    fn arity(&self) -> usize {
-        0
+    fn arity(&self) -> Arity {
+        Arity::exactly(0)
     }
 
-    fn call(&self, _: &mut Interpreter, __: Vec<Types>) -> Result<Types, String> {
+    fn call(&self, _: &mut Interpreter, __: Vec<Types>) -> Result<Types, RuntimeError> {
         let time = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
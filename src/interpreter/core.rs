@@ -1,38 +1,66 @@
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use std::rc::Rc;
 use std::mem;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::cell::RefCell;
 
 use ast::*;
 use lexer::*;
 use super::environment::Environment;
+use super::error::{RuntimeError, StackFrame};
+use super::list;
+use super::map::{self, MapKey};
+use catalog::ErrorCode;
+use symbol::Symbol;
 
 pub struct Interpreter {
     current_environment: Environment,
     global_environment: Environment,
     locals: HashMap<usize, usize>,
+    /// Spans of the calls currently being executed, innermost last.
+    call_sites: Vec<Span>,
+    /// The class of the instances runtime errors are caught as.
+    error_class: Rc<ClassData>,
 }
 
 impl Interpreter {
     pub fn new(output: Box<Fn(&str)>) -> Interpreter {
-        let global_environment = Environment::global(output);
+        let mut global_environment = Environment::global(output);
+        let error_class = Rc::new(ClassData {
+            name: Symbol::intern("Error"),
+            methods: HashMap::new(),
+            super_class: None,
+        });
+        global_environment.define(
+            &Token::Ident(error_class.name),
+            Some(Types::Class(Rc::new(Class {
+                class_data: error_class.clone(),
+            }))),
+        );
         Interpreter {
             current_environment: global_environment.clone(),
             global_environment: global_environment,
             locals: HashMap::new(),
+            call_sites: Vec::new(),
+            error_class,
         }
     }
 
-    pub fn interpret(&mut self, program: &[Statement]) -> Result<(), String> {
+    pub fn interpret(&mut self, program: &[Statement]) -> Result<(), RuntimeError> {
         for s in program {
             match self.visit_statement(s) {
                 Ok(()) => (),
                 Err(err) => match err {
                     CatBoxReturn::Err(error) => return Err(error),
                     CatBoxReturn::Return(_) => {
-                        return Err(String::from(
-                            "Return can only be used in function scope dummy",
+                        return Err(error(ErrorCode::ReturnOutsideFunction, &[], s.get_span()))
+                    }
+                    // The resolver rejects these outside of loops.
+                    CatBoxReturn::Break | CatBoxReturn::Continue => {
+                        return Err(error(
+                            ErrorCode::Internal,
+                            &["loop control outside of a loop"],
+                            s.get_span(),
                         ))
                     }
                 },
@@ -49,48 +77,177 @@ impl Interpreter {
         // Swap out environment with desired environment
         mem::swap(&mut self.current_environment, &mut environment);
 
+        let mut result = Ok(());
         for statement in block {
-            match self.visit_statement(statement) {
-                Ok(()) => (),
-                Err(value) => match value {
-                    CatBoxReturn::Err(s) => {
-                        return Err(CatBoxReturn::Err(s));
-                    }
-                    CatBoxReturn::Return(t) => {
-                        // Swap back current environment
-                        mem::swap(&mut self.current_environment, &mut environment);
-
-                        return Err(CatBoxReturn::Return(t));
-                    }
-                },
+            result = self.visit_statement(statement);
+            if result.is_err() {
+                break;
             }
         }
 
-        // Swap back current environment
+        // Swap back current environment, however the block was left
         mem::swap(&mut self.current_environment, &mut environment);
 
-        Ok(())
+        result
     }
 
     fn call_callable(
         &mut self,
         callee: &Callable,
         arguments: &Vec<Expression>,
-    ) -> Result<Types, String> {
-        if arguments.len() != callee.arity() {
-            return Err(String::from(format!(
-                "This wants {} arguments and you passed it {}, try again dipshit",
-                callee.arity(),
-                arguments.len()
-            )));
-        }
-
+        named: &Vec<(Token, Expression)>,
+        span: Span,
+    ) -> Result<Types, RuntimeError> {
         let mut interpreted_arguments: Vec<Types> = Vec::new();
         for expr in arguments {
             interpreted_arguments.push(self.visit_expression(expr)?);
         }
+        let mut named_arguments: Vec<(Symbol, Types)> = Vec::new();
+        for &(ref name, ref expr) in named {
+            match name {
+                &Token::Ident(name) => named_arguments.push((name, self.visit_expression(expr)?)),
+                _ => unreachable!(),
+            }
+        }
+
+        self.call_sites.push(span);
+        let result = callee.call_named(self, interpreted_arguments, named_arguments);
+        self.call_sites.pop();
+
+        result.map_err(|err| err.at(span))
+    }
+
+    /// The value a `catch` sees for `err`: what was thrown, or an `Error`
+    /// instance with the `message` of an error the interpreter raised and
+    /// its error code, e.g. `E0402`, as `kind`.
+    fn caught(&self, err: RuntimeError) -> Types {
+        if let Some(value) = err.thrown {
+            return value;
+        }
+        let instance = Instance {
+            class_data: self.error_class.clone(),
+            instance_data: Rc::new(RefCell::new(InstanceData {
+                fields: HashMap::new(),
+            })),
+        };
+        instance.set(Symbol::MESSAGE, Types::ReturnString(err.message));
+        instance.set(
+            Symbol::KIND,
+            Types::ReturnString(err.code.as_str().to_string()),
+        );
+        Types::Instance(instance)
+    }
+
+    /// Evaluate `expr` in `environment` instead of the current one.
+    fn evaluate_in(
+        &mut self,
+        expr: &Expression,
+        environment: &Environment,
+    ) -> Result<Types, RuntimeError> {
+        let mut environment = environment.clone();
+        mem::swap(&mut self.current_environment, &mut environment);
+        let result = self.visit_expression(expr);
+        mem::swap(&mut self.current_environment, &mut environment);
+        result
+    }
+
+    /// Evaluate a chain of calls, property accesses and indexing. `None`
+    /// means a `?.` found nil and the rest of the chain was skipped.
+    fn evaluate_chain(&mut self, e: &Expression) -> Result<Option<Types>, RuntimeError> {
+        let span = e.get_span();
+        let value = match e {
+            &Expression::Call {
+                ref callee,
+                ref arguments,
+                ref named,
+                ..
+            } => match self.evaluate_chain(callee)? {
+                Some(Types::Callable(inner)) => self.call_callable(&(**inner), arguments, named, span),
+                Some(Types::Class(inner)) => self.call_callable(&(*inner), arguments, named, span),
+                Some(other) => Err(error(ErrorCode::NotCallable, &[other.type_name()], span)),
+                None => return Ok(None),
+            },
+            &Expression::Get {
+                ref name,
+                ref object,
+                optional,
+                ..
+            } => match self.evaluate_chain(object)? {
+                Some(Types::Instance(mut instance)) => match name {
+                    &Token::Ident(name) => match instance.get(name) {
+                        Some(get_return) => Ok(get_return),
+                        None => Err(error(ErrorCode::UndefinedProperty, &[name.as_str()], span)),
+                    },
+                    _ => unreachable!(),
+                },
+                Some(Types::List(ref list)) => match name {
+                    &Token::Ident(name) => match list::method(list, name) {
+                        Some(method) => Ok(method),
+                        None => Err(error(ErrorCode::UndefinedProperty, &[name.as_str()], span)),
+                    },
+                    _ => unreachable!(),
+                },
+                Some(Types::Map(ref entries)) => match name {
+                    &Token::Ident(name) => match map::method(entries, name) {
+                        Some(method) => Ok(method),
+                        None => Err(error(ErrorCode::UndefinedProperty, &[name.as_str()], span)),
+                    },
+                    _ => unreachable!(),
+                },
+                Some(Types::Nil) if optional => return Ok(None),
+                Some(other) => Err(error(
+                    ErrorCode::PropertyOnNonInstance,
+                    &[other.type_name()],
+                    span,
+                )),
+                None => return Ok(None),
+            },
+            &Expression::Index {
+                ref object,
+                ref index,
+                ..
+            } => match self.evaluate_chain(object)? {
+                Some(Types::List(list)) => {
+                    let index = self.visit_expression(index)?;
+                    let list = list.borrow();
+                    let i = list::index(&index, list.len()).map_err(|err| err.at(span))?;
+                    Ok(list[i].clone())
+                }
+                Some(Types::Map(entries)) => {
+                    let key = map::key(&self.visit_expression(index)?).map_err(|err| err.at(span))?;
+                    match entries.borrow().get(&key) {
+                        Some(value) => Ok(value.clone()),
+                        None => Err(error(ErrorCode::KeyNotFound, &[&key.to_string()], span)),
+                    }
+                }
+                Some(other) => Err(error(ErrorCode::NotIndexable, &[other.type_name()], span)),
+                None => return Ok(None),
+            },
+            _ => self.visit_expression(e),
+        };
+        value.map(Some)
+    }
+
+    /// Call a function or class with arguments that are already evaluated,
+    /// for native functions taking callbacks.
+    pub fn call_value(
+        &mut self,
+        callee: &Types,
+        arguments: Vec<Types>,
+    ) -> Result<Types, RuntimeError> {
+        match callee {
+            &Types::Callable(ref inner) => inner.call_named(self, arguments, vec![]),
+            &Types::Class(ref inner) => inner.call_named(self, arguments, vec![]),
+            other => Err(RuntimeError::new(ErrorCode::NotCallable, &[other.type_name()])),
+        }
+    }
 
-        Ok(callee.call(self, interpreted_arguments)?)
+    /// The line of the innermost call being executed.
+    fn call_line(&self) -> usize {
+        match self.call_sites.last() {
+            Some(span) => span.line,
+            None => 0,
+        }
     }
 
     pub fn resolve(&mut self, expr: &Expression, i: usize) {
@@ -99,21 +256,23 @@ impl Interpreter {
 }
 
 impl MutVisitor for Interpreter {
-    type E = Result<Types, String>;
+    type E = Result<Types, RuntimeError>;
     type S = Result<(), CatBoxReturn>;
 
     fn visit_expression(&mut self, e: &Expression) -> Self::E {
+        let span = e.get_span();
         match e {
             &Expression::Assignment {
                 ref name, ref expr, ..
             } => {
                 let value = self.visit_expression(expr)?;
                 match self.locals.get(&e.get_id()) {
-                    Some(distance) => {
-                        self.current_environment
-                            .assign_at(*distance, &name, value.clone())?
-                    }
-                    None => self.global_environment.assign(&name, value.clone())?,
+                    Some(distance) => self.current_environment
+                        .assign_at(*distance, &name, value.clone())
+                        .map_err(|err| err.at(span))?,
+                    None => self.global_environment
+                        .assign(&name, value.clone())
+                        .map_err(|err| err.at(span))?,
                 };
                 Ok(value)
             }
@@ -125,77 +284,152 @@ impl MutVisitor for Interpreter {
             } => {
                 let right = self.visit_expression(r_expr)?;
                 let left = self.visit_expression(l_expr)?;
-                match (left, operator.clone(), right) {
-                    (Types::ReturnString(mut ls), Token::Plus, Types::ReturnString(rs)) => {
-                        ls.push_str(&rs);
-                        Ok(Types::ReturnString(ls))
-                    }
-                    (Types::Number(n), Token::Plus, Types::ReturnString(mut s))
-                    | (Types::ReturnString(mut s), Token::Plus, Types::Number(n)) => {
-                        s.push_str(&format!("{}", n));
-                        Ok(Types::ReturnString(s))
-                    }
-                    (Types::Number(ln), t, Types::Number(rn)) => match t {
-                        Token::Plus => Ok(Types::Number(ln + rn)),
-                        Token::Minus => Ok(Types::Number(ln - rn)),
-                        Token::Asterisk => Ok(Types::Number(ln * rn)),
-                        Token::Slash => if rn == 0.0 {
-                            Err(String::from("No cabrón, I will not divide by zero!"))
-                        } else {
-                            Ok(Types::Number(ln / rn))
-                        },
-                        Token::GreaterThan => Ok(Types::Boolean(ln > rn)),
-                        Token::GreaterEqual => Ok(Types::Boolean(ln >= rn)),
-                        Token::LessThan => Ok(Types::Boolean(ln < rn)),
-                        Token::LessEqual => Ok(Types::Boolean(ln <= rn)),
-                        Token::Equal => Ok(Types::Boolean(ln == rn)),
-                        Token::NotEqual => Ok(Types::Boolean(ln != rn)),
-                        _ => Err(String::from("NO! NO you can't do that! Fuuuuuuck!")),
-                    },
-                    (Types::Nil, t, Types::Nil) => match t {
-                        Token::Equal => Ok(Types::Boolean(true)),
-                        Token::NotEqual => Ok(Types::Boolean(false)),
-                        _ => Err(String::from("Fuck no you asshole I'm not doing that shit!")),
-                    },
-                    (Types::Boolean(lb), t, Types::Boolean(rb)) => match t {
-                        Token::Equal => Ok(Types::Boolean(lb == rb)),
-                        Token::NotEqual => Ok(Types::Boolean(lb != rb)),
-                        _ => Err(String::from("¡Chinga tu madre!")),
-                    },
-                    _ => Err(String::from("NO! NO! NO!")),
+                match operator {
+                    &Token::Equal => return Ok(Types::Boolean(is_equal(&left, &right))),
+                    &Token::NotEqual => return Ok(Types::Boolean(!is_equal(&left, &right))),
+                    _ => (),
                 }
+                binary(left, operator, right, span)
             }
-            &Expression::Call {
-                ref callee,
-                ref arguments,
+            &Expression::Call { .. } | &Expression::Get { .. } | &Expression::Index { .. } => {
+                Ok(self.evaluate_chain(e)?.unwrap_or(Types::Nil))
+            }
+            &Expression::Coalesce {
+                ref l_expr,
+                ref r_expr,
                 ..
-            } => match self.visit_expression(callee)? {
-                Types::Callable(inner) => self.call_callable(&(**inner), arguments),
-                Types::Class(inner) => self.call_callable(&(*inner), arguments),
-                _ => return Err(String::from("You can't call this shit!")),
+            } => match self.visit_expression(l_expr)? {
+                Types::Nil => self.visit_expression(r_expr),
+                value => Ok(value),
             },
-            &Expression::Get {
-                ref name,
-                ref object,
+            &Expression::Conditional {
+                ref condition,
+                ref then_expr,
+                ref else_expr,
                 ..
-            } => match self.visit_expression(object)? {
-                Types::Instance(mut instance) => match name {
-                    &Token::Ident(ref name) => match instance.get(name) {
-                        Some(get_return) => Ok(get_return),
-                        None => Err(format!("{} is a fucking undefined property!", name)),
-                    },
-                    _ => unreachable!(),
+            } => if is_truthy(&self.visit_expression(condition)?) {
+                self.visit_expression(then_expr)
+            } else {
+                self.visit_expression(else_expr)
+            },
+            &Expression::CompoundAssignment {
+                ref target,
+                ref operator,
+                ref value,
+                ..
+            } => match **target {
+                Expression::Variable { ref name, .. } => {
+                    let current = self.visit_expression(target)?;
+                    let value = self.visit_expression(value)?;
+                    let value = binary(current, operator, value, span)?;
+                    match self.locals.get(&target.get_id()) {
+                        Some(distance) => self.current_environment
+                            .assign_at(*distance, &name, value.clone())
+                            .map_err(|err| err.at(span))?,
+                        None => self.global_environment
+                            .assign(&name, value.clone())
+                            .map_err(|err| err.at(span))?,
+                    };
+                    Ok(value)
+                }
+                Expression::Get {
+                    ref name,
+                    ref object,
+                    ..
+                } => match (name, self.visit_expression(object)?) {
+                    (&Token::Ident(name), Types::Instance(instance)) => {
+                        let current = match instance.get(name) {
+                            Some(current) => current,
+                            None => {
+                                return Err(error(ErrorCode::UndefinedProperty, &[name.as_str()], span))
+                            }
+                        };
+                        let value = self.visit_expression(value)?;
+                        let value = binary(current, operator, value, span)?;
+                        instance.set(name, value.clone());
+                        Ok(value)
+                    }
+                    (_, other) => Err(error(
+                        ErrorCode::FieldOnNonInstance,
+                        &[other.type_name()],
+                        span,
+                    )),
                 },
-                _ => Err(String::from("Only instances have properties asshole!")),
+                Expression::Index {
+                    ref object,
+                    ref index,
+                    ..
+                } => match self.visit_expression(object)? {
+                    Types::List(list) => {
+                        let index = self.visit_expression(index)?;
+                        let current = {
+                            let list = list.borrow();
+                            let i = list::index(&index, list.len()).map_err(|err| err.at(span))?;
+                            list[i].clone()
+                        };
+                        let value = self.visit_expression(value)?;
+                        let value = binary(current, operator, value, span)?;
+                        // The right hand side may have resized the list.
+                        let mut list = list.borrow_mut();
+                        let len = list.len();
+                        let i = list::index(&index, len).map_err(|err| err.at(span))?;
+                        list[i] = value.clone();
+                        Ok(value)
+                    }
+                    Types::Map(entries) => {
+                        let key = map::key(&self.visit_expression(index)?).map_err(|err| err.at(span))?;
+                        let current = match entries.borrow().get(&key) {
+                            Some(current) => current.clone(),
+                            None => {
+                                return Err(error(ErrorCode::KeyNotFound, &[&key.to_string()], span))
+                            }
+                        };
+                        let value = self.visit_expression(value)?;
+                        let value = binary(current, operator, value, span)?;
+                        entries.borrow_mut().insert(key, value.clone());
+                        Ok(value)
+                    }
+                    other => Err(error(ErrorCode::NotIndexable, &[other.type_name()], span)),
+                },
+                _ => Err(error(ErrorCode::Internal, &["invalid compound assignment target"], span)),
             },
             &Expression::Grouping { ref expr, .. } => self.visit_expression(expr),
+            &Expression::Interpolation { ref parts, .. } => {
+                let mut result = String::new();
+                for part in parts {
+                    match self.visit_expression(part)? {
+                        Types::ReturnString(s) => result.push_str(&s),
+                        value => result.push_str(&value.to_string()),
+                    }
+                }
+                Ok(Types::ReturnString(result))
+            }
+            &Expression::Lambda {
+                ref parameters,
+                ref body,
+                ..
+            } => Ok(Types::Callable(Rc::new(Box::new(Function {
+                name: None,
+                class_name: None,
+                is_initializer: false,
+                parameters: parameters.clone(),
+                body: body.clone(),
+                closure: self.current_environment.clone(),
+            })))),
+            &Expression::List { ref elements, .. } => {
+                let mut list = Vec::with_capacity(elements.len());
+                for expr in elements {
+                    list.push(self.visit_expression(expr)?);
+                }
+                Ok(Types::List(Rc::new(RefCell::new(list))))
+            }
             &Expression::Literal { ref token, .. } => match token.clone() {
                 Token::Number(i) => Ok(Types::Number(i.into())),
                 Token::True => Ok(Types::Boolean(true)),
                 Token::False => Ok(Types::Boolean(false)),
                 Token::Nil => Ok(Types::Nil),
-                Token::LoxString(s) => Ok(Types::ReturnString(s)),
-                _ => Err(String::from("🐑💨")),
+                Token::LoxString(s) => Ok(Types::ReturnString(s.into_owned())),
+                t => Err(error(ErrorCode::InvalidLiteral, &[&t.to_string()], span)),
             },
             &Expression::Logical {
                 ref l_expr,
@@ -219,15 +453,14 @@ impl MutVisitor for Interpreter {
             }
             &Expression::This { .. } => {
                 if let Some(distance) = self.locals.get(&e.get_id()) {
-                    if let Some(instance) =
-                        self.current_environment.get_at(*distance, &Token::This)?
+                    if let Some(instance) = self.current_environment
+                        .get_at(*distance, &Token::This)
+                        .map_err(|err| err.at(span))?
                     {
                         return Ok(instance);
                     }
                 }
-                Err(String::from(
-                    "Internal interpreter error: shit is fucked with this",
-                ))
+                Err(error(ErrorCode::Internal, &["unresolved 'this'"], span))
             }
             &Expression::Unary {
                 ref operator,
@@ -241,7 +474,11 @@ impl MutVisitor for Interpreter {
                         Ok(Types::Boolean(true))
                     }
                     (_, Token::Bang) => Ok(Types::Boolean(false)),
-                    _ => Err(String::from("🖕🖕🖕🖕")),
+                    (r, t) => Err(error(
+                        ErrorCode::InvalidOperand,
+                        &[&t.to_string(), r.type_name()],
+                        span,
+                    )),
                 }
             }
             &Expression::Set {
@@ -250,30 +487,72 @@ impl MutVisitor for Interpreter {
                 ref value,
                 ..
             } => match (name, self.visit_expression(object)?) {
-                (&Token::Ident(ref name), Types::Instance(ref instance)) => {
+                (&Token::Ident(name), Types::Instance(ref instance)) => {
+                    let value = self.visit_expression(value)?;
+                    instance.set(name, value.clone());
+                    Ok(value)
+                }
+                (_, other) => Err(error(
+                    ErrorCode::FieldOnNonInstance,
+                    &[other.type_name()],
+                    span,
+                )),
+            },
+            &Expression::SetIndex {
+                ref object,
+                ref index,
+                ref value,
+                ..
+            } => match self.visit_expression(object)? {
+                Types::List(list) => {
+                    let index = self.visit_expression(index)?;
                     let value = self.visit_expression(value)?;
-                    instance.set(name.clone(), value.clone());
+                    let mut list = list.borrow_mut();
+                    let len = list.len();
+                    let i = list::index(&index, len).map_err(|err| err.at(span))?;
+                    list[i] = value.clone();
                     Ok(value)
                 }
-                _ => Err(String::from("Only instances have fields dumbass!")),
+                Types::Map(entries) => {
+                    let key = map::key(&self.visit_expression(index)?).map_err(|err| err.at(span))?;
+                    let value = self.visit_expression(value)?;
+                    entries.borrow_mut().insert(key, value.clone());
+                    Ok(value)
+                }
+                other => Err(error(ErrorCode::NotIndexable, &[other.type_name()], span)),
             },
+            &Expression::Map { ref entries, .. } => {
+                let mut map = BTreeMap::new();
+                for &(ref key_expr, ref value) in entries {
+                    let key = map::key(&self.visit_expression(key_expr)?)
+                        .map_err(|err| err.at(key_expr.get_span()))?;
+                    map.insert(key, self.visit_expression(value)?);
+                }
+                Ok(Types::Map(Rc::new(RefCell::new(map))))
+            }
             &Expression::Super { ref method, .. } => {
                 if let Some(distance) = self.locals.get(&e.get_id()) {
-                    if let Some(Types::Class(super_class)) =
-                        self.current_environment.get_at(*distance, &Token::Super)?
+                    if let Some(Types::Class(super_class)) = self.current_environment
+                        .get_at(*distance, &Token::Super)
+                        .map_err(|err| err.at(span))?
                     {
                         // "this" is always one level nearer than "super"'s environment.
                         if let Some(Types::Instance(instance)) = self.current_environment
-                            .get_at(*distance + 1, &Token::This)?
+                            .get_at(*distance + 1, &Token::This)
+                            .map_err(|err| err.at(span))?
                         {
                             match method {
-                                &Token::Ident(ref method) => {
+                                &Token::Ident(method) => {
                                     match super_class.class_data.find_method(method, &instance) {
                                         Some(thing) => {
                                             return Ok(thing);
                                         }
                                         None => {
-                                            return Err(format!("Undefined property {}", method));
+                                            return Err(error(
+                                                ErrorCode::UndefinedProperty,
+                                                &[method.as_str()],
+                                                span,
+                                            ));
                                         }
                                     }
                                 }
@@ -282,16 +561,20 @@ impl MutVisitor for Interpreter {
                         }
                     }
                 }
-                Err(String::from(
-                    "Internal interpreter error: shit is fucked with self",
-                ))
+                Err(error(ErrorCode::Internal, &["unresolved 'super'"], span))
             }
             &Expression::Variable { ref name, .. } => match self.locals.get(&e.get_id()) {
-                Some(distance) => match self.current_environment.get_at(*distance, name)? {
+                Some(distance) => match self.current_environment
+                    .get_at(*distance, name)
+                    .map_err(|err| err.at(span))?
+                {
                     Some(t) => Ok(t),
                     None => Ok(Types::Nil),
                 },
-                None => match self.global_environment.get(name)? {
+                None => match self.global_environment
+                    .get(name)
+                    .map_err(|err| err.at(span))?
+                {
                     Some(t) => Ok(t),
                     None => Ok(Types::Nil),
                 },
@@ -305,8 +588,9 @@ impl MutVisitor for Interpreter {
                 name: ref class_name,
                 ref methods,
                 ref super_class,
+                ..
             } => match class_name {
-                &Token::Ident(ref name_string) => {
+                &Token::Ident(name_symbol) => {
                     let mut super_environment = Environment::new_node(&self.current_environment);
                     let super_class_data_option = match super_class {
                         &Some(ref expr) => match self.visit_expression(expr)? {
@@ -323,8 +607,10 @@ impl MutVisitor for Interpreter {
                                 Some(class.class_data.clone())
                             }
                             _ => {
-                                return Err(CatBoxReturn::Err(String::from(
-                                    "Superclass must be a class!",
+                                return Err(CatBoxReturn::Err(error(
+                                    ErrorCode::SuperclassNotClass,
+                                    &[],
+                                    expr.get_span(),
                                 )))
                             }
                         },
@@ -334,13 +620,16 @@ impl MutVisitor for Interpreter {
                     let mut methods_map = HashMap::new();
                     for method_statement in methods {
                         match method_statement {
-                            &Statement::FunctionDeclaration(ref name, ref parameters, ref body) => {
+                            &Statement::FunctionDeclaration(ref name, ref parameters, ref body, _, _) => {
                                 let name = match name {
-                                    &Token::Ident(ref name) => name.clone(),
+                                    &Token::Ident(name) => name,
                                     _ => unreachable!(),
                                 };
 
                                 let method = Function {
+                                    name: Some(name),
+                                    class_name: Some(name_symbol),
+                                    is_initializer: name == Symbol::INIT,
                                     parameters: parameters.clone(),
                                     body: body.clone(),
                                     closure: self.current_environment.clone(),
@@ -357,7 +646,7 @@ impl MutVisitor for Interpreter {
                     }
 
                     let class_data = ClassData {
-                        name: name_string.clone(),
+                        name: name_symbol,
                         methods: methods_map,
                         super_class: super_class_data_option,
                     };
@@ -372,18 +661,26 @@ impl MutVisitor for Interpreter {
                 }
                 _ => unreachable!(),
             },
-            &Statement::Block(ref statements) => {
+            &Statement::Break(_) => Err(CatBoxReturn::Break),
+            &Statement::Continue(_) => Err(CatBoxReturn::Continue),
+            &Statement::Block(ref statements, _) => {
                 let mut environment = Environment::new_node(&self.current_environment);
 
                 self.execute_block(statements, environment)?;
                 Ok(())
             }
-            &Statement::Expression(ref expr) => {
+            &Statement::Expression(ref expr, _) => {
                 self.visit_expression(expr)?;
                 Ok(())
             }
-            &Statement::FunctionDeclaration(ref name_token, ref parameters, ref body) => {
+            &Statement::FunctionDeclaration(ref name_token, ref parameters, ref body, _, _) => {
                 let cbox_fn = Function {
+                    name: match name_token {
+                        &Token::Ident(name) => Some(name),
+                        _ => unreachable!(),
+                    },
+                    class_name: None,
+                    is_initializer: false,
                     parameters: parameters.clone(),
                     body: body.clone(),
                     closure: self.current_environment.clone(),
@@ -394,7 +691,7 @@ impl MutVisitor for Interpreter {
                 );
                 Ok(())
             }
-            &Statement::If(ref conditional, ref then, ref else_option) => {
+            &Statement::If(ref conditional, ref then, ref else_option, _) => {
                 if is_truthy(&self.visit_expression(conditional)?) {
                     self.visit_statement(then)?;
                 } else {
@@ -405,20 +702,61 @@ impl MutVisitor for Interpreter {
 
                 Ok(())
             }
-            &Statement::Return(ref expr_option) => Err(CatBoxReturn::Return(match expr_option {
+            &Statement::Return(ref expr_option, _) => Err(CatBoxReturn::Return(match expr_option {
                 &Some(ref expr) => self.visit_expression(expr)?,
                 &None => Types::Nil,
             })),
-            &Statement::VariableDeclaration(ref token, ref initializer) => match initializer {
+            &Statement::Throw(ref expr, span) => {
+                let value = self.visit_expression(expr)?;
+                let description = describe_thrown(&value);
+                Err(CatBoxReturn::Err(RuntimeError::thrown(value, &description).at(span)))
+            }
+            // `finally` runs however the body and handler were left, unless
+            // it is left early itself that outcome stands.
+            &Statement::Try {
+                ref body,
+                ref catch,
+                ref finally,
+                ..
+            } => {
+                let environment = Environment::new_node(&self.current_environment);
+                let mut result = self.execute_block(body, environment);
+
+                if let &Some((ref name, ref handler)) = catch {
+                    result = match result {
+                        Err(CatBoxReturn::Err(err)) => {
+                            let mut environment = Environment::new_node(&self.current_environment);
+                            environment.define(name, Some(self.caught(err)));
+                            self.execute_block(handler, environment)
+                        }
+                        other => other,
+                    };
+                }
+
+                if let &Some(ref finally) = finally {
+                    let environment = Environment::new_node(&self.current_environment);
+                    self.execute_block(finally, environment)?;
+                }
+
+                result
+            }
+            &Statement::VariableDeclaration(ref token, ref initializer, _, _) => match initializer {
                 &Some(ref e) => {
                     let result = self.visit_expression(e)?;
                     Ok(self.current_environment.define(&token, Some(result)))
                 }
                 &None => Ok(self.current_environment.define(&token, None)),
             },
-            &Statement::While(ref expr, ref stmt) => {
+            &Statement::While(ref expr, ref stmt, ref increment, _) => {
                 while is_truthy(&self.visit_expression(expr)?) {
-                    self.visit_statement(stmt)?
+                    match self.visit_statement(stmt) {
+                        Ok(()) | Err(CatBoxReturn::Continue) => (),
+                        Err(CatBoxReturn::Break) => break,
+                        Err(other) => return Err(other),
+                    }
+                    if let &Some(ref increment) = increment {
+                        self.visit_expression(increment)?;
+                    }
                 }
 
                 Ok(())
@@ -435,27 +773,81 @@ pub enum Types {
     Callable(Rc<Box<Callable>>),
     Class(Rc<Class>),
     Instance(Instance),
+    List(Rc<RefCell<Vec<Types>>>),
+    Map(Rc<RefCell<BTreeMap<MapKey, Types>>>),
     Nil,
 }
 
 pub enum CatBoxReturn {
-    Err(String),
+    Err(RuntimeError),
     Return(Types),
+    Break,
+    Continue,
 }
 
-impl From<String> for CatBoxReturn {
-    fn from(s: String) -> Self {
-        CatBoxReturn::Err(s)
+impl From<RuntimeError> for CatBoxReturn {
+    fn from(err: RuntimeError) -> Self {
+        CatBoxReturn::Err(err)
     }
 }
 
-impl Display for Types {
-    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+impl Types {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            &Types::Boolean(_) => "boolean",
+            &Types::Callable(_) => "function",
+            &Types::Class(_) => "class",
+            &Types::Instance(_) => "instance",
+            &Types::List(_) => "list",
+            &Types::Map(_) => "map",
+            &Types::Nil => "nil",
+            &Types::Number(_) => "number",
+            &Types::ReturnString(_) => "string",
+        }
+    }
+
+    /// `printing` holds the lists and maps being written further up, one
+    /// that contains itself is written as `[...]` or `{...}` the second
+    /// time round.
+    fn write(&self, f: &mut Formatter, printing: &mut Vec<*const ()>) -> FmtResult {
         match self {
             &Types::Boolean(b) => write!(f, "{}", b),
             &Types::Callable(ref c) => write!(f, "{}", c),
             &Types::Class(ref c) => write!(f, "{}", c),
             &Types::Instance(ref instance) => write!(f, "{}", instance),
+            &Types::List(ref list) => {
+                let pointer = Rc::as_ptr(list) as *const ();
+                if printing.contains(&pointer) {
+                    return write!(f, "[...]");
+                }
+                printing.push(pointer);
+                write!(f, "[")?;
+                for (i, item) in list.borrow().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    item.write(f, printing)?;
+                }
+                printing.pop();
+                write!(f, "]")
+            }
+            &Types::Map(ref entries) => {
+                let pointer = Rc::as_ptr(entries) as *const ();
+                if printing.contains(&pointer) {
+                    return write!(f, "{{...}}");
+                }
+                printing.push(pointer);
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.borrow().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: ", key)?;
+                    value.write(f, printing)?;
+                }
+                printing.pop();
+                write!(f, "}}")
+            }
             &Types::Nil => write!(f, "nil"),
             &Types::Number(n) => write!(f, "{}", n),
             &Types::ReturnString(ref s) => write!(f, "\"{}\"", s.to_string()),
@@ -463,12 +855,198 @@ impl Display for Types {
     }
 }
 
+impl Display for Types {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.write(f, &mut vec![])
+    }
+}
+
+/// How many arguments a callable accepts, `max` is `None` when there's no
+/// upper limit.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Arity {
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+impl Arity {
+    pub fn exactly(count: usize) -> Arity {
+        Arity {
+            min: count,
+            max: Some(count),
+        }
+    }
+
+    pub fn at_least(count: usize) -> Arity {
+        Arity {
+            min: count,
+            max: None,
+        }
+    }
+
+    pub fn accepts(&self, count: usize) -> bool {
+        count >= self.min && match self.max {
+            Some(max) => count <= max,
+            None => true,
+        }
+    }
+}
+
+impl Display for Arity {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self.max {
+            Some(max) if max == self.min => write!(f, "{}", max),
+            Some(max) => write!(f, "{} to {}", self.min, max),
+            None => write!(f, "at least {}", self.min),
+        }
+    }
+}
+
 pub trait Callable: Debug + Display {
-    fn arity(&self) -> usize;
-    fn call(&self, &mut Interpreter, Vec<Types>) -> Result<Types, String>;
+    fn arity(&self) -> Arity;
+    /// Only called with a number of arguments `arity` accepts.
+    fn call(&self, &mut Interpreter, Vec<Types>) -> Result<Types, RuntimeError>;
+
+    /// Call with `name: value` arguments after the positional ones. Only
+    /// functions written in cat-lox have named parameters, by default any
+    /// named argument is rejected.
+    fn call_named(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Types>,
+        named: Vec<(Symbol, Types)>,
+    ) -> Result<Types, RuntimeError> {
+        if let Some(&(name, _)) = named.first() {
+            return Err(RuntimeError::new(
+                ErrorCode::UnknownParameter,
+                &[&self.to_string(), name.as_str()],
+            ));
+        }
+        check_arity(self.arity(), arguments.len())?;
+        self.call(interpreter, arguments)
+    }
+}
+
+fn error(code: ErrorCode, args: &[&str], span: Span) -> RuntimeError {
+    RuntimeError::new(code, args).at(span)
+}
+
+fn check_arity(arity: Arity, count: usize) -> Result<(), RuntimeError> {
+    if !arity.accepts(count) {
+        return Err(RuntimeError::new(
+            ErrorCode::ArityMismatch,
+            &[&arity.to_string(), &count.to_string()],
+        ));
+    }
+    Ok(())
+}
+
+/// Apply an arithmetic or comparison operator, `==` and `!=` go through `is_equal`.
+fn binary(left: Types, operator: &Token, right: Types, span: Span) -> Result<Types, RuntimeError> {
+    match (left, operator.clone(), right) {
+        (Types::ReturnString(mut ls), Token::Plus, Types::ReturnString(rs)) => {
+            ls.push_str(&rs);
+            Ok(Types::ReturnString(ls))
+        }
+        (Types::Number(n), Token::Plus, Types::ReturnString(mut s))
+        | (Types::ReturnString(mut s), Token::Plus, Types::Number(n)) => {
+            s.push_str(&format!("{}", n));
+            Ok(Types::ReturnString(s))
+        }
+        (Types::Number(ln), t, Types::Number(rn)) => match t {
+            Token::Plus => Ok(Types::Number(ln + rn)),
+            Token::Minus => Ok(Types::Number(ln - rn)),
+            Token::Asterisk => Ok(Types::Number(ln * rn)),
+            Token::Slash => {
+                if rn == 0.0 {
+                    Err(error(ErrorCode::DivisionByZero, &[], span))
+                } else {
+                    Ok(Types::Number(ln / rn))
+                }
+            }
+            // The remainder takes the sign of the dividend, `-7 % 3` is `-1`.
+            Token::Percent => {
+                if rn == 0.0 {
+                    Err(error(ErrorCode::DivisionByZero, &[], span))
+                } else {
+                    Ok(Types::Number(ln % rn))
+                }
+            }
+            Token::Power => Ok(Types::Number(ln.powf(rn))),
+            Token::GreaterThan => Ok(Types::Boolean(ln > rn)),
+            Token::GreaterEqual => Ok(Types::Boolean(ln >= rn)),
+            Token::LessThan => Ok(Types::Boolean(ln < rn)),
+            Token::LessEqual => Ok(Types::Boolean(ln <= rn)),
+            t => Err(error(
+                ErrorCode::InvalidNumberOperator,
+                &[&t.to_string()],
+                span,
+            )),
+        },
+        (Types::Nil, t, Types::Nil) => Err(error(
+            ErrorCode::InvalidNilOperator,
+            &[&t.to_string()],
+            span,
+        )),
+        (Types::Boolean(_), t, Types::Boolean(_)) => Err(error(
+            ErrorCode::InvalidBooleanOperator,
+            &[&t.to_string()],
+            span,
+        )),
+        // Strings order lexicographically.
+        (Types::ReturnString(ls), t, Types::ReturnString(rs)) => match t {
+            Token::GreaterThan => Ok(Types::Boolean(ls > rs)),
+            Token::GreaterEqual => Ok(Types::Boolean(ls >= rs)),
+            Token::LessThan => Ok(Types::Boolean(ls < rs)),
+            Token::LessEqual => Ok(Types::Boolean(ls <= rs)),
+            t => Err(error(
+                ErrorCode::InvalidOperands,
+                &[&t.to_string(), "string", "string"],
+                span,
+            )),
+        },
+        (l, t, r) => Err(error(
+            ErrorCode::InvalidOperands,
+            &[&t.to_string(), l.type_name(), r.type_name()],
+            span,
+        )),
+    }
+}
+
+/// How an uncaught `throw` of `value` is reported. Instances with a string
+/// `message`, like caught runtime errors, report that message.
+fn describe_thrown(value: &Types) -> String {
+    if let &Types::Instance(ref instance) = value {
+        if let Some(Types::ReturnString(message)) =
+            instance.instance_data.borrow().get(Symbol::MESSAGE)
+        {
+            return format!("{}: {}", instance.class_data.name, message);
+        }
+    }
+    value.to_string()
+}
+
+/// Values of different types are never equal. Numbers, strings, booleans
+/// and nil compare by value, everything else is the same only if it's the
+/// very same object.
+fn is_equal(left: &Types, right: &Types) -> bool {
+    match (left, right) {
+        (&Types::Boolean(l), &Types::Boolean(r)) => l == r,
+        (&Types::Nil, &Types::Nil) => true,
+        (&Types::Number(l), &Types::Number(r)) => l == r,
+        (&Types::ReturnString(ref l), &Types::ReturnString(ref r)) => l == r,
+        (&Types::Callable(ref l), &Types::Callable(ref r)) => Rc::ptr_eq(l, r),
+        (&Types::Class(ref l), &Types::Class(ref r)) => Rc::ptr_eq(&l.class_data, &r.class_data),
+        (&Types::Instance(ref l), &Types::Instance(ref r)) => {
+            Rc::ptr_eq(&l.instance_data, &r.instance_data)
+        }
+        (&Types::List(ref l), &Types::List(ref r)) => Rc::ptr_eq(l, r),
+        (&Types::Map(ref l), &Types::Map(ref r)) => Rc::ptr_eq(l, r),
+        _ => false,
+    }
 }
 
-fn is_truthy(expression_return: &Types) -> bool {
+pub fn is_truthy(expression_return: &Types) -> bool {
     match expression_return {
         &Types::Nil | &Types::Boolean(false) => false,
         _ => true,
@@ -477,7 +1055,13 @@ fn is_truthy(expression_return: &Types) -> bool {
 
 #[derive(Debug)]
 pub struct Function {
-    parameters: Vec<Token>,
+    /// `None` for anonymous functions.
+    name: Option<Symbol>,
+    /// The class this function is a method of, if any.
+    class_name: Option<Symbol>,
+    /// Initializers always return the instance they were called on.
+    is_initializer: bool,
+    parameters: Vec<Parameter>,
     body: Vec<Statement>,
     closure: Environment,
 }
@@ -487,37 +1071,150 @@ impl Function {
         let mut environment = Environment::new_node(&self.closure);
         environment.define(&Token::This, Some(instance));
         Function {
+            name: self.name,
+            class_name: self.class_name,
+            is_initializer: self.is_initializer,
             parameters: self.parameters.clone(),
             body: self.body.clone(),
             closure: environment,
         }
     }
+
+    fn display_name(&self) -> String {
+        match self.name {
+            Some(name) => name.to_string(),
+            None => "<lambda>".to_string(),
+        }
+    }
+
+    /// The environment for a call, every parameter bound to its positional
+    /// argument, its named argument or failing those its default.
+    fn bind_arguments(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Types>,
+        named: Vec<(Symbol, Types)>,
+    ) -> Result<Environment, RuntimeError> {
+        let arity = self.arity();
+        if let Some(max) = arity.max {
+            if arguments.len() > max {
+                return Err(RuntimeError::new(
+                    ErrorCode::ArityMismatch,
+                    &[&arity.to_string(), &arguments.len().to_string()],
+                ));
+            }
+        }
+
+        let mut named_arguments = HashMap::new();
+        for (name, value) in named {
+            let position = self.parameters.iter().position(|p| match p.name {
+                Token::Ident(parameter) => parameter == name && !p.rest,
+                _ => false,
+            });
+            match position {
+                None => {
+                    return Err(RuntimeError::new(
+                        ErrorCode::UnknownParameter,
+                        &[&self.display_name(), name.as_str()],
+                    ))
+                }
+                Some(i) if i < arguments.len() || named_arguments.contains_key(&name) => {
+                    return Err(RuntimeError::new(
+                        ErrorCode::DuplicateArgument,
+                        &[name.as_str()],
+                    ))
+                }
+                Some(_) => {
+                    named_arguments.insert(name, value);
+                }
+            }
+        }
+
+        let mut environment = Environment::new_node(&self.closure);
+        let mut arguments = arguments.into_iter();
+        for parameter in &self.parameters {
+            let name = match parameter.name {
+                Token::Ident(name) => name,
+                _ => unreachable!(),
+            };
+            let value = if parameter.rest {
+                Types::List(Rc::new(RefCell::new(arguments.by_ref().collect())))
+            } else if let Some(value) = arguments.next() {
+                value
+            } else if let Some(value) = named_arguments.remove(&name) {
+                value
+            } else if let Some(ref default) = parameter.default {
+                interpreter.evaluate_in(default, &environment)?
+            } else {
+                return Err(RuntimeError::new(
+                    ErrorCode::MissingArgument,
+                    &[name.as_str()],
+                ));
+            };
+            environment.define(&parameter.name, Some(value));
+        }
+        Ok(environment)
+    }
 }
 
 impl Callable for Function {
-    fn arity(&self) -> usize {
-        self.parameters.len()
+    /// Parameters with defaults can be left out from the last one without.
+    fn arity(&self) -> Arity {
+        let required = self.parameters
+            .iter()
+            .rposition(|p| !p.rest && p.default.is_none())
+            .map_or(0, |i| i + 1);
+        match self.parameters.last() {
+            Some(last) if last.rest => Arity::at_least(required),
+            _ => Arity {
+                min: required,
+                max: Some(self.parameters.len()),
+            },
+        }
     }
 
     fn call(
         &self,
         interpreter: &mut Interpreter,
-        mut arguments: Vec<Types>,
-    ) -> Result<Types, String> {
-        let mut environment = Environment::new_node(&self.closure);
+        arguments: Vec<Types>,
+    ) -> Result<Types, RuntimeError> {
+        self.call_named(interpreter, arguments, vec![])
+    }
 
-        // Define parameters as passed arguments
-        for (i, arg) in arguments.drain(..).enumerate() {
-            environment.define(&self.parameters[i], Some(arg));
-        }
+    fn call_named(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Types>,
+        named: Vec<(Symbol, Types)>,
+    ) -> Result<Types, RuntimeError> {
+        let environment = self.bind_arguments(interpreter, arguments, named)?;
 
-        match interpreter.execute_block(&self.body, environment) {
-            Ok(()) => Ok(Types::Nil),
+        let value = match interpreter.execute_block(&self.body, environment) {
+            Ok(()) => Types::Nil,
             Err(value) => match value {
-                CatBoxReturn::Err(s) => Err(s),
-                CatBoxReturn::Return(t) => Ok(t),
+                CatBoxReturn::Err(err) => return Err(err.unwind(StackFrame {
+                    function: self.display_name(),
+                    class: self.class_name.map(|name| name.to_string()),
+                    line: interpreter.call_line(),
+                })),
+                CatBoxReturn::Return(t) => t,
+                // The resolver rejects loop control that isn't inside a loop
+                // of the function itself.
+                CatBoxReturn::Break | CatBoxReturn::Continue => {
+                    return Err(RuntimeError::new(
+                        ErrorCode::Internal,
+                        &["loop control escaped a function"],
+                    ))
+                }
             },
+        };
+
+        if self.is_initializer {
+            // `bind` put "this" in the innermost scope of the closure.
+            let this = self.closure.get_at(self.closure.innermost(), &Token::This)?;
+            return Ok(this.unwrap_or(Types::Nil));
         }
+        Ok(value)
     }
 }
 
@@ -534,41 +1231,56 @@ pub struct Class {
 
 #[derive(Debug)]
 struct ClassData {
-    name: String,
-    methods: HashMap<String, Function>,
+    name: Symbol,
+    methods: HashMap<Symbol, Function>,
     super_class: Option<Rc<ClassData>>,
 }
 
 impl ClassData {
-    fn find_method(&self, name: &str, instance: &Instance) -> Option<Types> {
-        match self.methods.get(name) {
+    /// Look `name` up in this class, then up the inheritance chain.
+    fn find(&self, name: Symbol) -> Option<&Function> {
+        match self.methods.get(&name) {
+            Some(method) => Some(method),
+            None => match self.super_class {
+                Some(ref super_class) => super_class.find(name),
+                None => None,
+            },
+        }
+    }
+
+    fn find_method(&self, name: Symbol, instance: &Instance) -> Option<Types> {
+        match self.find(name) {
             Some(method) => {
                 let new_method = method.bind(Types::Instance(instance.clone()));
                 Some(Types::Callable(Rc::new(Box::new(new_method))))
             }
-            None => match self.super_class {
-                Some(ref super_class) => match super_class.methods.get(name) {
-                    Some(method) => {
-                        let new_method = method.bind(Types::Instance(instance.clone()));
-                        Some(Types::Callable(Rc::new(Box::new(new_method))))
-                    }
-                    None => None,
-                },
-                None => None,
-            },
+            None => None,
         }
     }
 }
 
 impl Callable for Class {
-    fn arity(&self) -> usize {
-        if let Some(initializer) = self.class_data.methods.get("init") {
+    fn arity(&self) -> Arity {
+        if let Some(initializer) = self.class_data.find(Symbol::INIT) {
             return initializer.arity();
         }
-        0
+        Arity::exactly(0)
     }
 
-    fn call(&self, interpreter: &mut Interpreter, arguments: Vec<Types>) -> Result<Types, String> {
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Types>,
+    ) -> Result<Types, RuntimeError> {
+        self.call_named(interpreter, arguments, vec![])
+    }
+
+    fn call_named(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Types>,
+        named: Vec<(Symbol, Types)>,
+    ) -> Result<Types, RuntimeError> {
         let instance_data = InstanceData {
             fields: HashMap::new(),
         };
@@ -578,10 +1290,22 @@ impl Callable for Class {
             instance_data: Rc::new(RefCell::new(instance_data)),
         });
 
-        if let Some(initializer) = self.class_data.methods.get("init") {
-            initializer
-                .bind(instance.clone())
-                .call(interpreter, arguments)?;
+        match self.class_data.find(Symbol::INIT) {
+            Some(initializer) => {
+                initializer
+                    .bind(instance.clone())
+                    .call_named(interpreter, arguments, named)?;
+            }
+            // Nothing takes the arguments, there mustn't be any.
+            None => {
+                if let Some(&(name, _)) = named.first() {
+                    return Err(RuntimeError::new(
+                        ErrorCode::UnknownParameter,
+                        &[&self.to_string(), name.as_str()],
+                    ));
+                }
+                check_arity(Arity::exactly(0), arguments.len())?;
+            }
         }
 
         Ok(instance)
@@ -596,18 +1320,18 @@ impl Display for Class {
 
 #[derive(Debug)]
 struct InstanceData {
-    fields: HashMap<String, Types>,
+    fields: HashMap<Symbol, Types>,
 }
 
 impl InstanceData {
-    fn get(&self, name: &str) -> Option<Types> {
-        match self.fields.get(name) {
+    fn get(&self, name: Symbol) -> Option<Types> {
+        match self.fields.get(&name) {
             Some(return_value) => Some(return_value.clone()),
             None => None,
         }
     }
 
-    fn set(&mut self, name: String, value: Types) {
+    fn set(&mut self, name: Symbol, value: Types) {
         self.fields.insert(name, value);
     }
 }
@@ -619,14 +1343,14 @@ pub struct Instance {
 }
 
 impl Instance {
-    fn get(&self, name: &str) -> Option<Types> {
+    fn get(&self, name: Symbol) -> Option<Types> {
         match self.instance_data.borrow().get(name) {
             some @ Some(_) => some,
             None => self.class_data.find_method(name, self),
         }
     }
 
-    fn set(&self, name: String, value: Types) {
+    fn set(&self, name: Symbol, value: Types) {
         self.instance_data.borrow_mut().set(name, value)
     }
 }
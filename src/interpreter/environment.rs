@@ -1,8 +1,11 @@
 use super::core::Types;
+use super::error::RuntimeError;
+use catalog::ErrorCode;
 use super::clock::Clock;
 use super::print::Print;
 use lexer::Token;
 use std::collections::HashMap;
+use symbol::Symbol;
 use std::rc::Rc;
 use std::cell::RefCell;
 
@@ -29,19 +32,20 @@ impl Environment {
         new_environment
     }
 
-    pub fn assign(&mut self, name: &Token, value: Types) -> Result<(), String> {
+    /// The distance of the innermost scope, for the `_at` lookups.
+    pub fn innermost(&self) -> usize {
+        self.cactus_stack.len() - 1
+    }
+
+    pub fn assign(&mut self, name: &Token, value: Types) -> Result<(), RuntimeError> {
         match name {
-            &Token::Ident(ref name) => {
+            &Token::Ident(name) => {
                 for e in self.cactus_stack.iter_mut().rev() {
                     if let Some(_) = e.borrow_mut().assign(name, &value) {
                         return Ok(());
                     }
                 }
-                Err(String::from(format!(
-                    "{} is undefined why would you try to assign \
-                     to something that hasn't been defined?!?!",
-                    name
-                )))
+                Err(RuntimeError::new(ErrorCode::AssignToUndefined, &[name.as_str()]))
             }
             _ => unreachable!(),
         }
@@ -52,9 +56,9 @@ impl Environment {
         distance: usize,
         name_token: &Token,
         value: Types,
-    ) -> Result<(), String> {
+    ) -> Result<(), RuntimeError> {
         match name_token {
-            &Token::Ident(ref name) => {
+            &Token::Ident(name) => {
                 if let Some(_) = self.cactus_stack[distance]
                     .borrow_mut()
                     .assign(name, &value)
@@ -62,10 +66,12 @@ impl Environment {
                     return Ok(());
                 }
 
-                Err(format!(
-                    "Internal interpreter error: can't assign {} at the {} level of the environment",
-                    name,
-                    distance
+                Err(RuntimeError::new(
+                    ErrorCode::Internal,
+                    &[&format!(
+                        "can't assign {} at the {} level of the environment",
+                        name, distance
+                    )],
                 ))
             }
             _ => unreachable!(),
@@ -74,7 +80,7 @@ impl Environment {
 
     pub fn define(&mut self, name: &Token, value: Option<Types>) -> () {
         match name {
-            &Token::Ident(ref name) => {
+            &Token::Ident(name) => {
                 let len = self.cactus_stack.len();
                 self.cactus_stack[len - 1].borrow_mut().define(name, value)
             }
@@ -82,59 +88,72 @@ impl Environment {
                 let len = self.cactus_stack.len();
                 self.cactus_stack[len - 1]
                     .borrow_mut()
-                    .define("this", value)
+                    .define(Symbol::THIS, value)
             }
             &Token::Super => {
                 let len = self.cactus_stack.len();
                 self.cactus_stack[len - 1]
                     .borrow_mut()
-                    .define("super", value)
+                    .define(Symbol::SUPER, value)
             }
             _ => unreachable!(),
         }
     }
 
-    pub fn get(&self, name_token: &Token) -> Result<Option<Types>, String> {
+    pub fn get(&self, name_token: &Token) -> Result<Option<Types>, RuntimeError> {
         match name_token {
-            &Token::Ident(ref name) => {
+            &Token::Ident(name) => {
                 for e in self.cactus_stack.iter().rev() {
                     if let Some(value) = e.borrow().get(name) {
                         return Ok(value);
                     }
                 }
-                Err(format!("{} is super fucking undefined", name))
+                Err(RuntimeError::new(ErrorCode::UndefinedVariable, &[name.as_str()]))
             }
             _ => unreachable!(),
         }
     }
 
-    pub fn get_at(&self, distance: usize, name_token: &Token) -> Result<Option<Types>, String> {
+    pub fn get_at(
+        &self,
+        distance: usize,
+        name_token: &Token,
+    ) -> Result<Option<Types>, RuntimeError> {
         match name_token {
-            &Token::Ident(ref name) => {
+            &Token::Ident(name) => {
                 if let Some(value) = self.cactus_stack[distance].borrow().get(name) {
                     return Ok(value);
                 }
-                Err(format!(
-                    "Internal interpreter error: can't get {} at the {} level of the environment",
-                    name, distance
+                Err(RuntimeError::new(
+                    ErrorCode::Internal,
+                    &[&format!(
+                        "can't get {} at the {} level of the environment",
+                        name, distance
+                    )],
                 ))
             }
             &Token::This => {
-                if let Some(value) = self.cactus_stack[distance].borrow().get("this") {
+                if let Some(value) = self.cactus_stack[distance].borrow().get(Symbol::THIS) {
                     return Ok(value);
                 }
-                Err(format!(
-                    "Internal interpreter error: can't get \"this\" at the {} level of the environment",
-                     distance
+                Err(RuntimeError::new(
+                    ErrorCode::Internal,
+                    &[&format!(
+                        "can't get \"this\" at the {} level of the environment",
+                        distance
+                    )],
                 ))
             }
             &Token::Super => {
-                if let Some(value) = self.cactus_stack[distance].borrow().get("super") {
+                if let Some(value) = self.cactus_stack[distance].borrow().get(Symbol::SUPER) {
                     return Ok(value);
                 }
-                Err(format!(
-                    "Internal interpreter error: can't get \"super\" at the {} level of the environment",
-                     distance
+                Err(RuntimeError::new(
+                    ErrorCode::Internal,
+                    &[&format!(
+                        "can't get \"super\" at the {} level of the environment",
+                        distance
+                    )],
                 ))
             }
             _ => unreachable!(),
@@ -144,7 +163,7 @@ impl Environment {
 
 #[derive(Debug)]
 struct EnvironmentNode {
-    values: HashMap<String, Option<Types>>,
+    values: HashMap<Symbol, Option<Types>>,
 }
 
 impl EnvironmentNode {
@@ -164,27 +183,27 @@ impl EnvironmentNode {
 
         let print = Types::Callable(Rc::new(Box::new(Print::new(output))));
 
-        global.define("clock", Some(clock));
-        global.define("print", Some(print));
+        global.define(Symbol::intern("clock"), Some(clock));
+        global.define(Symbol::intern("print"), Some(print));
 
         global
     }
 
-    fn assign(&mut self, name: &str, value: &Types) -> Option<()> {
-        if self.values.contains_key(name) {
-            self.values.insert(String::from(name), Some(value.clone()));
+    fn assign(&mut self, name: Symbol, value: &Types) -> Option<()> {
+        if self.values.contains_key(&name) {
+            self.values.insert(name, Some(value.clone()));
             Some(())
         } else {
             None
         }
     }
 
-    fn define(&mut self, name: &str, value: Option<Types>) -> () {
-        self.values.insert(String::from(name), value);
+    fn define(&mut self, name: Symbol, value: Option<Types>) -> () {
+        self.values.insert(name, value);
     }
 
-    fn get(&self, name: &str) -> Option<Option<Types>> {
-        match self.values.get(name) {
+    fn get(&self, name: Symbol) -> Option<Option<Types>> {
+        match self.values.get(&name) {
             Some(e) => Some(e.clone()),
             None => None,
         }
@@ -0,0 +1,129 @@
+use catalog::ErrorCode;
+use lexer::Span;
+use super::core::Types;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RuntimeErrorKind {
+    ArityMismatch,
+    DivisionByZero,
+    IndexOutOfBounds,
+    Internal,
+    KeyNotFound,
+    NotCallable,
+    NotAnInstance,
+    /// A value raised by `throw`.
+    Thrown,
+    TypeError,
+    UndefinedProperty,
+    UndefinedVariable,
+}
+
+impl RuntimeErrorKind {
+    fn of(code: ErrorCode) -> RuntimeErrorKind {
+        match code {
+            ErrorCode::ArityMismatch
+            | ErrorCode::MissingArgument
+            | ErrorCode::UnknownParameter
+            | ErrorCode::DuplicateArgument => RuntimeErrorKind::ArityMismatch,
+            ErrorCode::DivisionByZero => RuntimeErrorKind::DivisionByZero,
+            ErrorCode::IndexOutOfBounds => RuntimeErrorKind::IndexOutOfBounds,
+            ErrorCode::KeyNotFound => RuntimeErrorKind::KeyNotFound,
+            ErrorCode::InvalidOperands
+            | ErrorCode::InvalidOperand
+            | ErrorCode::InvalidNumberOperator
+            | ErrorCode::InvalidNilOperator
+            | ErrorCode::InvalidBooleanOperator
+            | ErrorCode::InvalidIndex
+            | ErrorCode::NotIndexable
+            | ErrorCode::InvalidMapKey
+            | ErrorCode::SuperclassNotClass => RuntimeErrorKind::TypeError,
+            ErrorCode::NotCallable => RuntimeErrorKind::NotCallable,
+            ErrorCode::PropertyOnNonInstance | ErrorCode::FieldOnNonInstance => {
+                RuntimeErrorKind::NotAnInstance
+            }
+            ErrorCode::UncaughtException => RuntimeErrorKind::Thrown,
+            ErrorCode::UndefinedProperty => RuntimeErrorKind::UndefinedProperty,
+            ErrorCode::UndefinedVariable | ErrorCode::AssignToUndefined => {
+                RuntimeErrorKind::UndefinedVariable
+            }
+            _ => RuntimeErrorKind::Internal,
+        }
+    }
+}
+
+/// A function that was executing when a runtime error unwound through it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StackFrame {
+    pub function: String,
+    /// The class the function is a method of, if any.
+    pub class: Option<String>,
+    /// The line the function was called from.
+    pub line: usize,
+}
+
+impl Display for StackFrame {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self.class {
+            Some(ref class) => write!(f, "in {}.{}", class, self.function)?,
+            None => write!(f, "in {}", self.function)?,
+        }
+        write!(f, ", called from line {}", self.line)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RuntimeError {
+    pub kind: RuntimeErrorKind,
+    pub code: ErrorCode,
+    pub message: String,
+    pub span: Option<Span>,
+    /// Innermost frame first.
+    pub stack: Vec<StackFrame>,
+    /// The value given to `throw`, errors raised by the interpreter itself
+    /// are turned into an `Error` instance when they are caught instead.
+    pub thrown: Option<Types>,
+}
+
+impl RuntimeError {
+    pub fn new(code: ErrorCode, args: &[&str]) -> RuntimeError {
+        RuntimeError {
+            kind: RuntimeErrorKind::of(code),
+            code,
+            message: code.message(args),
+            span: None,
+            stack: vec![],
+            thrown: None,
+        }
+    }
+
+    /// A `throw` of `value`, `description` is reported if nothing catches it.
+    pub fn thrown(value: Types, description: &str) -> RuntimeError {
+        let mut err = RuntimeError::new(ErrorCode::UncaughtException, &[description]);
+        err.thrown = Some(value);
+        err
+    }
+
+    /// Attach a location, unless a more precise one was attached already.
+    pub fn at(mut self, span: Span) -> RuntimeError {
+        if self.span.is_none() {
+            self.span = Some(span);
+        }
+        self
+    }
+
+    /// Record a frame the error is unwinding through.
+    pub fn unwind(mut self, frame: StackFrame) -> RuntimeError {
+        self.stack.push(frame);
+        self
+    }
+}
+
+impl Display for RuntimeError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self.span {
+            Some(span) => write!(f, "{}: {}", span, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Built-in list methods. Accessing a property of a list looks the name up
+//! here and hands back a callable bound to that list.
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::rc::Rc;
+
+use catalog::ErrorCode;
+use symbol::Symbol;
+use super::core::{is_truthy, Arity, Callable, Interpreter, Types};
+use super::error::RuntimeError;
+
+#[derive(Debug, Clone, Copy)]
+enum Method {
+    Filter,
+    Insert,
+    Len,
+    Map,
+    Pop,
+    Push,
+    Reduce,
+    Remove,
+    Slice,
+    Sort,
+}
+
+#[derive(Debug)]
+pub struct ListMethod {
+    list: Rc<RefCell<Vec<Types>>>,
+    method: Method,
+}
+
+/// The method called `name` bound to `list`, if lists have one.
+pub fn method(list: &Rc<RefCell<Vec<Types>>>, name: Symbol) -> Option<Types> {
+    let method = match name {
+        Symbol::FILTER => Method::Filter,
+        Symbol::INSERT => Method::Insert,
+        Symbol::LEN => Method::Len,
+        Symbol::MAP => Method::Map,
+        Symbol::POP => Method::Pop,
+        Symbol::PUSH => Method::Push,
+        Symbol::REDUCE => Method::Reduce,
+        Symbol::REMOVE => Method::Remove,
+        Symbol::SLICE => Method::Slice,
+        Symbol::SORT => Method::Sort,
+        _ => return None,
+    };
+    Some(Types::Callable(Rc::new(Box::new(ListMethod {
+        list: list.clone(),
+        method,
+    }))))
+}
+
+/// Check that `index` is a whole number below `len`.
+pub fn index(index: &Types, len: usize) -> Result<usize, RuntimeError> {
+    match index {
+        &Types::Number(n) if n.fract() == 0.0 => if n >= 0.0 && n < len as f64 {
+            Ok(n as usize)
+        } else {
+            Err(RuntimeError::new(
+                ErrorCode::IndexOutOfBounds,
+                &[&n.to_string(), &len.to_string()],
+            ))
+        },
+        &Types::Number(n) => Err(RuntimeError::new(ErrorCode::InvalidIndex, &[&n.to_string()])),
+        other => Err(RuntimeError::new(ErrorCode::InvalidIndex, &[other.type_name()])),
+    }
+}
+
+/// Numbers and strings sort among themselves, anything else can't be ordered.
+fn compare(a: &Types, b: &Types) -> Result<Ordering, RuntimeError> {
+    match (a, b) {
+        (&Types::Number(a), &Types::Number(b)) => Ok(a.partial_cmp(&b).unwrap_or(Ordering::Equal)),
+        (&Types::ReturnString(ref a), &Types::ReturnString(ref b)) => Ok(a.cmp(b)),
+        (a, b) => Err(RuntimeError::new(
+            ErrorCode::InvalidOperands,
+            &["<", a.type_name(), b.type_name()],
+        )),
+    }
+}
+
+impl Callable for ListMethod {
+    fn arity(&self) -> Arity {
+        match self.method {
+            Method::Len | Method::Pop | Method::Sort => Arity::exactly(0),
+            Method::Filter | Method::Map | Method::Remove => Arity::exactly(1),
+            Method::Insert | Method::Reduce => Arity::exactly(2),
+            // The end defaults to the end of the list.
+            Method::Slice => Arity {
+                min: 1,
+                max: Some(2),
+            },
+            // Pushes every argument in order.
+            Method::Push => Arity::at_least(1),
+        }
+    }
+
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        mut arguments: Vec<Types>,
+    ) -> Result<Types, RuntimeError> {
+        let mut arguments = arguments.drain(..);
+        let mut argument = || arguments.next().unwrap_or(Types::Nil);
+        let len = self.list.borrow().len();
+
+        match self.method {
+            Method::Len => Ok(Types::Number(len as f64)),
+            Method::Pop => Ok(self.list.borrow_mut().pop().unwrap_or(Types::Nil)),
+            Method::Push => {
+                self.list.borrow_mut().extend(arguments);
+                Ok(Types::Nil)
+            }
+            Method::Insert => {
+                // Inserting right after the last element is fine.
+                let at = index(&argument(), len + 1)?;
+                self.list.borrow_mut().insert(at, argument());
+                Ok(Types::Nil)
+            }
+            Method::Remove => {
+                let at = index(&argument(), len)?;
+                Ok(self.list.borrow_mut().remove(at))
+            }
+            Method::Slice => {
+                let start = index(&argument(), len + 1)?;
+                let end = match argument() {
+                    Types::Nil => len,
+                    end => index(&end, len + 1)?,
+                };
+                let slice = if start < end {
+                    self.list.borrow()[start..end].to_vec()
+                } else {
+                    vec![]
+                };
+                Ok(Types::List(Rc::new(RefCell::new(slice))))
+            }
+            // Callbacks run on a copy, they are free to change the list.
+            Method::Map => {
+                let function = argument();
+                let items = self.list.borrow().clone();
+                let mut mapped = Vec::with_capacity(items.len());
+                for item in items {
+                    mapped.push(interpreter.call_value(&function, vec![item])?);
+                }
+                Ok(Types::List(Rc::new(RefCell::new(mapped))))
+            }
+            Method::Filter => {
+                let function = argument();
+                let items = self.list.borrow().clone();
+                let mut kept = vec![];
+                for item in items {
+                    if is_truthy(&interpreter.call_value(&function, vec![item.clone()])?) {
+                        kept.push(item);
+                    }
+                }
+                Ok(Types::List(Rc::new(RefCell::new(kept))))
+            }
+            Method::Reduce => {
+                let function = argument();
+                let mut accumulator = argument();
+                let items = self.list.borrow().clone();
+                for item in items {
+                    accumulator = interpreter.call_value(&function, vec![accumulator, item])?;
+                }
+                Ok(accumulator)
+            }
+            Method::Sort => {
+                let mut items = self.list.borrow().clone();
+                let mut result = Ok(());
+                items.sort_by(|a, b| match compare(a, b) {
+                    Ok(ordering) => ordering,
+                    Err(err) => {
+                        result = Err(err);
+                        Ordering::Equal
+                    }
+                });
+                result?;
+                *self.list.borrow_mut() = items;
+                Ok(Types::Nil)
+            }
+        }
+    }
+}
+
+impl Display for ListMethod {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "native list method")
+    }
+}
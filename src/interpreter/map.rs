@@ -0,0 +1,155 @@
+//! Maps and their built-in methods, bound the same way as list methods.
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::rc::Rc;
+
+use catalog::ErrorCode;
+use symbol::Symbol;
+use super::core::{Arity, Callable, Interpreter, Types};
+use super::error::RuntimeError;
+
+/// The values that can key a map. Keys are ordered booleans first, then
+/// numbers, then strings, which is the order `keys()` returns them in.
+#[derive(Debug, Clone)]
+pub enum MapKey {
+    Boolean(bool),
+    Number(f64),
+    String(String),
+}
+
+impl MapKey {
+    fn rank(&self) -> u8 {
+        match self {
+            &MapKey::Boolean(_) => 0,
+            &MapKey::Number(_) => 1,
+            &MapKey::String(_) => 2,
+        }
+    }
+
+    pub fn value(&self) -> Types {
+        match self {
+            &MapKey::Boolean(b) => Types::Boolean(b),
+            &MapKey::Number(n) => Types::Number(n),
+            &MapKey::String(ref s) => Types::ReturnString(s.clone()),
+        }
+    }
+}
+
+// NaN is never a key, so numbers are totally ordered.
+impl Ord for MapKey {
+    fn cmp(&self, other: &MapKey) -> Ordering {
+        match (self, other) {
+            (&MapKey::Boolean(a), &MapKey::Boolean(b)) => a.cmp(&b),
+            (&MapKey::Number(a), &MapKey::Number(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            (&MapKey::String(ref a), &MapKey::String(ref b)) => a.cmp(b),
+            (a, b) => a.rank().cmp(&b.rank()),
+        }
+    }
+}
+
+impl PartialOrd for MapKey {
+    fn partial_cmp(&self, other: &MapKey) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for MapKey {
+    fn eq(&self, other: &MapKey) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MapKey {}
+
+impl Display for MapKey {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.value())
+    }
+}
+
+/// The key `value` stands for, if it can be one.
+pub fn key(value: &Types) -> Result<MapKey, RuntimeError> {
+    match value {
+        &Types::Boolean(b) => Ok(MapKey::Boolean(b)),
+        &Types::Number(n) if !n.is_nan() => {
+            // -0 and 0 are the same key.
+            Ok(MapKey::Number(n + 0.0))
+        }
+        &Types::ReturnString(ref s) => Ok(MapKey::String(s.clone())),
+        &Types::Number(_) => Err(RuntimeError::new(ErrorCode::InvalidMapKey, &["NaN"])),
+        other => Err(RuntimeError::new(ErrorCode::InvalidMapKey, &[other.type_name()])),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Method {
+    Has,
+    Keys,
+    Len,
+    Remove,
+    Values,
+}
+
+#[derive(Debug)]
+pub struct MapMethod {
+    map: Rc<RefCell<BTreeMap<MapKey, Types>>>,
+    method: Method,
+}
+
+/// The method called `name` bound to `map`, if maps have one.
+pub fn method(map: &Rc<RefCell<BTreeMap<MapKey, Types>>>, name: Symbol) -> Option<Types> {
+    let method = match name {
+        Symbol::HAS => Method::Has,
+        Symbol::KEYS => Method::Keys,
+        Symbol::LEN => Method::Len,
+        Symbol::REMOVE => Method::Remove,
+        Symbol::VALUES => Method::Values,
+        _ => return None,
+    };
+    Some(Types::Callable(Rc::new(Box::new(MapMethod {
+        map: map.clone(),
+        method,
+    }))))
+}
+
+impl Callable for MapMethod {
+    fn arity(&self) -> Arity {
+        match self.method {
+            Method::Keys | Method::Len | Method::Values => Arity::exactly(0),
+            Method::Has | Method::Remove => Arity::exactly(1),
+        }
+    }
+
+    fn call(&self, _: &mut Interpreter, arguments: Vec<Types>) -> Result<Types, RuntimeError> {
+        match self.method {
+            Method::Has => {
+                let key = key(&arguments[0])?;
+                Ok(Types::Boolean(self.map.borrow().contains_key(&key)))
+            }
+            Method::Keys => {
+                let keys = self.map.borrow().keys().map(|k| k.value()).collect();
+                Ok(Types::List(Rc::new(RefCell::new(keys))))
+            }
+            Method::Len => Ok(Types::Number(self.map.borrow().len() as f64)),
+            // Removing a key that isn't there is fine, there's just nothing
+            // to hand back.
+            Method::Remove => {
+                let key = key(&arguments[0])?;
+                Ok(self.map.borrow_mut().remove(&key).unwrap_or(Types::Nil))
+            }
+            Method::Values => {
+                let values = self.map.borrow().values().cloned().collect();
+                Ok(Types::List(Rc::new(RefCell::new(values))))
+            }
+        }
+    }
+}
+
+impl Display for MapMethod {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "native map method")
+    }
+}
@@ -1,6 +1,10 @@
 pub use self::core::Interpreter;
+pub use self::error::{RuntimeError, RuntimeErrorKind, StackFrame};
 
 mod clock;
 mod core;
 mod environment;
+mod error;
+mod list;
+mod map;
 mod print;
@@ -1,5 +1,6 @@
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
-use super::core::{Callable, Interpreter, Types};
+use super::core::{Arity, Callable, Interpreter, Types};
+use super::error::RuntimeError;
 
 pub struct Print {
     output_fn: Box<Fn(&str)>,
@@ -14,11 +15,11 @@ impl Print {
 }
 
 impl Callable for Print {
-    fn arity(&self) -> usize {
-        1
+    fn arity(&self) -> Arity {
+        Arity::exactly(1)
     }
 
-    fn call(&self, _: &mut Interpreter, params: Vec<Types>) -> Result<Types, String> {
+    fn call(&self, _: &mut Interpreter, params: Vec<Types>) -> Result<Types, RuntimeError> {
         (*self.output_fn)(&format!("{}", params[0]));
         Ok(Types::Nil)
     }
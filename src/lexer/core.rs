@@ -1,27 +1,272 @@
+use super::error::LexError;
 use super::token::*;
+use std::borrow::Cow;
+use symbol::Symbol;
 
-pub struct Lexer {
-    input: Vec<char>,
-    index: usize,
+/// Walks the source by byte offset, token payloads borrow slices of it
+/// rather than being assembled a character at a time.
+pub struct Lexer<'a> {
+    source: &'a str,
+    /// Byte offset of the token being scanned.
+    start: usize,
+    offset: usize,
+    line: usize,
+    column: usize,
+    /// One entry per `${` we're inside of, counting the braces opened
+    /// since so we know which `}` goes back to the string.
+    interpolations: Vec<usize>,
+    /// Emit whitespace and comments as tokens instead of skipping them.
+    trivia: bool,
 }
 
-impl Iterator for Lexer {
-    type Item = Token;
+impl<'a> Iterator for Lexer<'a> {
+    type Item = SpannedToken<'a>;
 
-    fn next(&mut self) -> Option<Token> {
+    fn next(&mut self) -> Option<SpannedToken<'a>> {
+        if let Some(trivia) = self.skip_whitespace() {
+            return Some(trivia);
+        }
+
+        let (line, column) = (self.line, self.column);
+        self.start = self.offset;
+        let token = match self.scan_token() {
+            Some(token) => token,
+            None if !self.interpolations.is_empty() => {
+                self.interpolations.clear();
+                Token::Illegal(LexError::UnterminatedString)
+            }
+            None => return None,
+        };
+
+        Some(SpannedToken {
+            token,
+            span: Span {
+                start: self.start,
+                end: self.offset,
+                line,
+                column,
+                end_line: self.line,
+                end_column: self.column,
+            },
+        })
+    }
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Lexer<'a> {
+        Lexer {
+            source: input,
+            start: 0,
+            offset: 0,
+            line: 1,
+            column: 1,
+            interpolations: vec![],
+            trivia: false,
+        }
+    }
+
+    /// A lexer that keeps whitespace and comments as `Whitespace` and
+    /// `Comment` tokens, so the tokens cover every byte of the input.
+    pub fn lossless(input: &'a str) -> Lexer<'a> {
+        Lexer {
+            trivia: true,
+            ..Lexer::new(input)
+        }
+    }
+
+    /// Skip whitespace and comments, they don't produce tokens. Doc comments
+    /// are left in place for `scan_token`. In lossless mode each run of
+    /// whitespace and each comment is returned as a token instead.
+    ///
+    /// An unterminated block comment swallows the rest of the input and is
+    /// returned as an illegal token.
+    fn skip_whitespace(&mut self) -> Option<SpannedToken<'a>> {
+        loop {
+            let (start, line, column) = (self.offset, self.line, self.column);
+            let comment = match (self.peek(), self.peek_next()) {
+                (Some(c), _) if is_whitespace(c) => {
+                    while self.peek().map_or(false, is_whitespace) {
+                        self.advance();
+                    }
+                    false
+                }
+                (Some('/'), Some('/')) if self.is_doc_comment() => return None,
+                (Some('/'), Some('/')) => {
+                    while let Some(current_char) = self.peek() {
+                        if current_char == '\n' {
+                            break;
+                        }
+                        self.advance();
+                    }
+                    true
+                }
+                (Some('/'), Some('*')) => {
+                    if !self.block_comment() {
+                        return Some(SpannedToken {
+                            token: Token::Illegal(LexError::UnterminatedComment),
+                            span: Span {
+                                start,
+                                end: self.offset,
+                                line,
+                                column,
+                                end_line: self.line,
+                                end_column: self.column,
+                            },
+                        });
+                    }
+                    true
+                }
+                _ => return None,
+            };
+
+            if self.trivia {
+                let text = Cow::Borrowed(&self.source[start..self.offset]);
+                return Some(SpannedToken {
+                    token: if comment {
+                        Token::Comment(text)
+                    } else {
+                        Token::Whitespace(text)
+                    },
+                    span: Span {
+                        start,
+                        end: self.offset,
+                        line,
+                        column,
+                        end_line: self.line,
+                        end_column: self.column,
+                    },
+                });
+            }
+        }
+    }
+
+    /// `///` starts a doc comment, but `////` and longer are plain comments.
+    fn is_doc_comment(&self) -> bool {
+        let rest = &self.source[self.offset..];
+        rest.starts_with("///") && !rest.starts_with("////")
+    }
+
+    /// Consume a block comment including any nested ones, returns false if
+    /// the input ends before it is closed.
+    fn block_comment(&mut self) -> bool {
+        let mut depth = 0;
+        loop {
+            match (self.peek(), self.peek_next()) {
+                (Some('/'), Some('*')) => {
+                    self.advance();
+                    self.advance();
+                    depth += 1;
+                }
+                (Some('*'), Some('/')) => {
+                    self.advance();
+                    self.advance();
+                    depth -= 1;
+                    if depth == 0 {
+                        return true;
+                    }
+                }
+                (Some(_), _) => {
+                    self.advance();
+                }
+                (None, _) => return false,
+            }
+        }
+    }
+
+    /// The text of a doc comment after its `///`, up to the end of the line.
+    fn doc_comment(&mut self) -> Token<'a> {
+        self.advance();
+        self.advance();
+        if self.peek() == Some(' ') {
+            self.advance();
+        }
+        let start = self.offset;
+        while let Some(next) = self.peek() {
+            if next == '\n' {
+                break;
+            }
+            self.advance();
+        }
+        Token::DocComment(Cow::Borrowed(self.source[start..self.offset].trim_end()))
+    }
+
+    fn scan_token(&mut self) -> Option<Token<'a>> {
         match self.advance() {
             None => None,
             // Operators
-            Some('+') => Some(Token::Plus),
-            Some('-') => Some(Token::Minus),
-            Some('*') => Some(Token::Asterisk),
+            Some('+') => match self.peek() {
+                Some('=') => {
+                    self.advance();
+                    Some(Token::PlusAssign)
+                }
+                _ => Some(Token::Plus),
+            },
+            Some('-') => match self.peek() {
+                Some('=') => {
+                    self.advance();
+                    Some(Token::MinusAssign)
+                }
+                _ => Some(Token::Minus),
+            },
+            Some('*') => match self.peek() {
+                Some('*') => {
+                    self.advance();
+                    Some(Token::Power)
+                }
+                Some('=') => {
+                    self.advance();
+                    Some(Token::AsteriskAssign)
+                }
+                _ => Some(Token::Asterisk),
+            },
+            Some('%') => match self.peek() {
+                Some('=') => {
+                    self.advance();
+                    Some(Token::PercentAssign)
+                }
+                _ => Some(Token::Percent),
+            },
             Some('(') => Some(Token::LeftParentheses),
             Some(')') => Some(Token::RightParentheses),
+            Some('[') => Some(Token::LeftBracket),
+            Some(']') => Some(Token::RightBracket),
+            Some(':') => Some(Token::Colon),
+            Some('?') => match self.peek() {
+                Some('?') => {
+                    self.advance();
+                    Some(Token::QuestionQuestion)
+                }
+                Some('.') => {
+                    self.advance();
+                    Some(Token::QuestionDot)
+                }
+                _ => Some(Token::Question),
+            },
             Some(',') => Some(Token::Comma),
             Some(';') => Some(Token::Semicolon),
-            Some('{') => Some(Token::LeftBrace),
-            Some('}') => Some(Token::RightBrace),
-            Some('.') => Some(Token::Dot),
+            Some('{') => {
+                if let Some(depth) = self.interpolations.last_mut() {
+                    *depth += 1;
+                }
+                Some(Token::LeftBrace)
+            }
+            Some('}') => match self.interpolations.pop() {
+                // Closes the interpolation, carry on with the string.
+                Some(0) => Some(self.string(true)),
+                Some(depth) => {
+                    self.interpolations.push(depth - 1);
+                    Some(Token::RightBrace)
+                }
+                None => Some(Token::RightBrace),
+            },
+            Some('.') => match (self.peek(), self.peek_next()) {
+                (Some('.'), Some('.')) => {
+                    self.advance();
+                    self.advance();
+                    Some(Token::Ellipsis)
+                }
+                _ => Some(Token::Dot),
+            },
             Some('<') => match self.peek() {
                 Some('=') => {
                     self.advance();
@@ -41,6 +286,10 @@ impl Iterator for Lexer {
                     self.advance();
                     Some(Token::Equal)
                 }
+                Some('>') => {
+                    self.advance();
+                    Some(Token::Arrow)
+                }
                 _ => Some(Token::Assign),
             },
             Some('!') => match self.peek() {
@@ -50,111 +299,267 @@ impl Iterator for Lexer {
                 }
                 _ => Some(Token::Bang),
             },
-            Some('/') => {
-                match self.peek() {
-                    // comments
-                    Some('/') => {
-                        self.advance();
-                        while let Some(current_char) = self.advance() {
-                            if current_char == '\n' {
-                                break;
-                            }
-                        }
-                        self.next()
-                    }
-                    _ => Some(Token::Slash),
+            Some('/') => match self.peek() {
+                // Plain comments were skipped, this must be a doc comment.
+                Some('/') => Some(self.doc_comment()),
+                Some('=') => {
+                    self.advance();
+                    Some(Token::SlashAssign)
+                }
+                _ => Some(Token::Slash),
+            },
+            Some('"') => Some(self.string(false)),
+            Some(current_char) if current_char.is_digit(10) => Some(self.number(current_char)),
+            Some(current_char) if is_identifier_start(current_char) => {
+                while self.peek().map_or(false, is_identifier_part) {
+                    self.advance();
+                }
+
+                let literal = &self.source[self.start..self.offset];
+                match keyword(literal) {
+                    Some(keyword) => Some(keyword),
+                    None => Some(Token::Ident(Symbol::intern(literal))),
                 }
             }
-            Some('"') => {
-                let mut literal = String::new();
-                while let Some(current_char) = self.advance() {
-                    if current_char == '"' {
-                        break;
+            Some(current_char) => Some(Token::Illegal(LexError::UnexpectedCharacter(
+                current_char,
+            ))),
+        }
+    }
+
+    /// Scan a string up to its closing quote, or up to the next `${` in
+    /// which case the segment is returned as an `Interpolation` and the
+    /// string resumes at the matching `}`. A string `resumed` after a `}`
+    /// gives the segment kinds the parser expects to follow an embedded
+    /// expression.
+    fn string(&mut self, resumed: bool) -> Token<'a> {
+        let source = self.source;
+        let content_start = self.offset;
+        // Only built once an escape means the source can't be used as is.
+        let mut decoded: Option<String> = None;
+        // Keep going after a bad escape so the rest of the string doesn't
+        // get lexed as code, but remember the first problem.
+        let mut error = None;
+        loop {
+            let content_end = self.offset;
+            let segment = |decoded: Option<String>| match decoded {
+                Some(text) => Cow::Owned(text),
+                None => Cow::Borrowed(&source[content_start..content_end]),
+            };
+            match self.advance() {
+                Some('"') => {
+                    return match error {
+                        Some(err) => Token::Illegal(err),
+                        None if resumed => Token::InterpolationEnd(segment(decoded.take())),
+                        None => Token::LoxString(segment(decoded.take())),
+                    }
+                }
+                Some('$') if self.peek() == Some('{') => {
+                    self.advance();
+                    self.interpolations.push(0);
+                    return match error {
+                        Some(err) => Token::Illegal(err),
+                        None if resumed => Token::InterpolationMiddle(segment(decoded.take())),
+                        None => Token::Interpolation(segment(decoded.take())),
+                    };
+                }
+                Some('\\') => {
+                    let mut text = segment(decoded.take()).into_owned();
+                    match self.escape() {
+                        Ok(c) => text.push(c),
+                        Err(err) => if error.is_none() {
+                            error = Some(err)
+                        },
                     }
-                    literal.push(current_char);
+                    decoded = Some(text);
+                }
+                Some(current_char) => if let Some(ref mut text) = decoded {
+                    text.push(current_char)
+                },
+                None => {
+                    // Any open interpolations went down with this string.
+                    self.interpolations.clear();
+                    return Token::Illegal(LexError::UnterminatedString);
                 }
-                Some(Token::LoxString(literal))
             }
-            // Whitespace (must be checked after comments)
-            Some(' ') => self.next(),
-            Some('\t') => self.next(),
-            Some('\r') => self.next(),
-            Some('\n') => self.next(),
-            // literal, keyword, or number
-            Some(current_char) => {
-                // Todo: maybe it would be preferable to store a reference to a
-                // slice rather than storing a new heap allocated string.
-                let mut literal = String::new();
-                literal.push(current_char);
-
-                loop {
-                    match self.peek() {
-                        Some(next) => {
-                            if is_blacklisted(&next) {
-                                break;
-                            }
-                            if !is_part_of_number(current_char) && next == '.' {
-                                break;
+        }
+    }
+
+    /// Decode an escape sequence, the backslash has already been consumed.
+    fn escape(&mut self) -> Result<char, LexError> {
+        match self.advance() {
+            Some('n') => Ok('\n'),
+            Some('t') => Ok('\t'),
+            Some('r') => Ok('\r'),
+            Some('0') => Ok('\0'),
+            Some('\\') => Ok('\\'),
+            Some('"') => Ok('"'),
+            Some('$') => Ok('$'),
+            Some('u') => {
+                let mut sequence = String::from("\\u");
+                if self.peek() == Some('{') {
+                    sequence.push('{');
+                    self.advance();
+                    while let Some(next) = self.peek() {
+                        if !next.is_digit(16) {
+                            break;
+                        }
+                        sequence.push(next);
+                        self.advance();
+                    }
+                    if self.peek() == Some('}') {
+                        sequence.push('}');
+                        self.advance();
+                        let hex = &sequence[3..sequence.len() - 1];
+                        if !hex.is_empty() && hex.len() <= 6 {
+                            if let Some(c) =
+                                u32::from_str_radix(hex, 16).ok().and_then(::std::char::from_u32)
+                            {
+                                return Ok(c);
                             }
                         }
-                        None => break,
                     }
+                }
+                Err(LexError::InvalidUnicodeEscape(sequence))
+            }
+            Some(other) => Err(LexError::UnknownEscape(format!("\\{}", other))),
+            // Let the caller report the unterminated string.
+            None => Ok('\\'),
+        }
+    }
 
-                    if let Some(current_char) = self.advance() {
-                        literal.push(current_char);
-                    }
+    /// Decimal literals with an optional fraction and exponent, or `0x`,
+    /// `0b` and `0o` integers. Digits may be separated by `_`.
+    fn number(&mut self, first: char) -> Token<'a> {
+        let radix = match (first, self.peek()) {
+            ('0', Some('x')) | ('0', Some('X')) => 16,
+            ('0', Some('b')) | ('0', Some('B')) => 2,
+            ('0', Some('o')) | ('0', Some('O')) => 8,
+            _ => 10,
+        };
+
+        let mut valid = if radix == 10 {
+            self.decimal()
+        } else {
+            self.advance();
+            self.digits(radix)
+        };
+
+        // Anything glued onto the end, like the second '.' in 1.2.3 or
+        // the letters in 12ab, makes the whole thing malformed.
+        valid &= match (self.peek(), self.peek_next()) {
+            (Some('.'), Some(next)) => !next.is_digit(10),
+            (Some(next), _) => !is_identifier_part(next),
+            _ => true,
+        };
+        if !valid {
+            while let Some(next) = self.peek() {
+                if !is_identifier_part(next) && next != '.' {
+                    break;
                 }
+                self.advance();
+            }
+            let literal = &self.source[self.start..self.offset];
+            return Token::Illegal(LexError::MalformedNumber(literal.to_string()));
+        }
+
+        let literal = &self.source[self.start..self.offset];
+        let digits = if literal.contains('_') {
+            literal.replace('_', "")
+        } else {
+            literal.to_string()
+        };
+        if radix == 10 {
+            Token::Number(digits.parse::<f64>().unwrap())
+        } else {
+            let value = digits[2..]
+                .chars()
+                .fold(0.0, |value, c| value * radix as f64 + c.to_digit(radix).unwrap() as f64);
+            Token::Number(value)
+        }
+    }
+
+    /// The rest of a decimal literal after its first digit.
+    fn decimal(&mut self) -> bool {
+        let mut valid = self.digits(10);
+
+        if let (Some('.'), Some(next)) = (self.peek(), self.peek_next()) {
+            if next.is_digit(10) {
+                self.advance();
+                valid &= self.digits(10);
+            }
+        }
 
-                if keyword(&literal).is_some() {
-                    keyword(&literal)
-                } else if literal.chars().all(is_part_of_number) {
-                    Some(Token::Number(literal.parse::<f64>().unwrap()))
-                } else {
-                    Some(Token::Ident(literal))
+        if let (Some(e), Some(next)) = (self.peek(), self.peek_next()) {
+            if (e == 'e' || e == 'E') && (next.is_digit(10) || next == '+' || next == '-') {
+                self.advance();
+                if next == '+' || next == '-' {
+                    self.advance();
                 }
+                valid &= self.digits(10);
             }
         }
+
+        valid
     }
-}
 
-impl Lexer {
-    pub fn new(input: &str) -> Lexer {
-        Lexer {
-            input: input.chars().collect(),
-            index: 0,
+    /// Consume digits in the given radix along with `_` separators. Returns
+    /// false unless it ends on a digit and every `_` follows one.
+    fn digits(&mut self, radix: u32) -> bool {
+        let mut valid = true;
+        let mut previous = self.source[..self.offset].chars().next_back();
+        while let Some(next) = self.peek() {
+            if next == '_' {
+                valid &= previous.map_or(false, |c| c.is_digit(radix));
+            } else if !next.is_digit(radix) {
+                break;
+            }
+            self.advance();
+            previous = Some(next);
         }
+        valid && previous.map_or(false, |c| c.is_digit(radix))
     }
 
     fn advance(&mut self) -> Option<char> {
-        if self.index >= self.input.len() {
-            None
+        let current_char = self.peek()?;
+        self.offset += current_char.len_utf8();
+        if current_char == '\n' {
+            self.line += 1;
+            self.column = 1;
         } else {
-            self.index += 1;
-            Some(self.input[self.index - 1])
+            self.column += 1;
         }
+        Some(current_char)
     }
 
     fn peek(&self) -> Option<char> {
-        if self.index >= self.input.len() {
-            None
-        } else {
-            Some(self.input[self.index])
+        self.char_at(self.offset)
+    }
+
+    fn peek_next(&self) -> Option<char> {
+        let current_char = self.peek()?;
+        self.char_at(self.offset + current_char.len_utf8())
+    }
+
+    /// The character starting at a byte offset, without decoding when it's
+    /// ASCII as nearly all source is.
+    fn char_at(&self, offset: usize) -> Option<char> {
+        match self.source.as_bytes().get(offset) {
+            Some(&byte) if byte.is_ascii() => Some(byte as char),
+            Some(_) => self.source[offset..].chars().next(),
+            None => None,
         }
     }
 }
 
-/// Is this char allowed to be in a literal?
-///
-/// TODO: if we ever need to add a new state, both this and the next
-/// function above need to be changed. That violates the open closed
-/// principle, investigate refactoring.
-fn is_blacklisted(c: &char) -> bool {
-    let blacklist = vec![
-        '+', '-', '*', '<', '>', '(', ')', ',', ';', '{', '}', '=', '!', '/', ' ', '\t', '\r', '\n'
-    ];
-    blacklist.contains(c)
+fn is_whitespace(c: char) -> bool {
+    c == ' ' || c == '\t' || c == '\r' || c == '\n'
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
 }
 
-fn is_part_of_number(c: char) -> bool {
-    c.is_digit(10) || c == '.'
+fn is_identifier_part(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
 }
@@ -0,0 +1,40 @@
+use catalog::ErrorCode;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum LexError {
+    UnterminatedString,
+    UnexpectedCharacter(char),
+    MalformedNumber(String),
+    UnknownEscape(String),
+    InvalidUnicodeEscape(String),
+    UnterminatedComment,
+}
+
+impl LexError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            &LexError::UnterminatedString => ErrorCode::UnterminatedString,
+            &LexError::UnexpectedCharacter(_) => ErrorCode::UnexpectedCharacter,
+            &LexError::MalformedNumber(_) => ErrorCode::MalformedNumber,
+            &LexError::UnknownEscape(_) => ErrorCode::UnknownEscape,
+            &LexError::InvalidUnicodeEscape(_) => ErrorCode::InvalidUnicodeEscape,
+            &LexError::UnterminatedComment => ErrorCode::UnterminatedComment,
+        }
+    }
+}
+
+impl Display for LexError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let message = match self {
+            &LexError::UnterminatedString | &LexError::UnterminatedComment => {
+                self.code().message(&[])
+            }
+            &LexError::UnexpectedCharacter(c) => self.code().message(&[&c.to_string()]),
+            &LexError::MalformedNumber(ref literal)
+            | &LexError::UnknownEscape(ref literal)
+            | &LexError::InvalidUnicodeEscape(ref literal) => self.code().message(&[literal]),
+        };
+        write!(f, "{}", message)
+    }
+}
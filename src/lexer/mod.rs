@@ -1,5 +1,7 @@
 pub use self::core::Lexer;
-pub use self::token::Token;
+pub use self::error::LexError;
+pub use self::token::{Span, SpannedToken, Token};
 
 mod core;
+mod error;
 mod token;
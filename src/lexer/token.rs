@@ -1,20 +1,53 @@
+use super::error::LexError;
+use symbol::Symbol;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Text payloads borrow from the source where they can, only strings with
+/// escapes in them need decoding into a buffer of their own.
 #[derive(Debug, PartialEq, Clone)]
-pub enum Token {
+pub enum Token<'a> {
     // Characters
-    Illegal,
+    Illegal(LexError),
     // Identifiers + literals
-    Ident(String),
-    LoxString(String),
+    Ident(Symbol),
+    LoxString(Cow<'a, str>),
+    /// A string segment followed by `${`, the rest of the string comes
+    /// back as `InterpolationMiddle` or `InterpolationEnd` after the `}`.
+    Interpolation(Cow<'a, str>),
+    /// A segment between a `}` and the next `${`.
+    InterpolationMiddle(Cow<'a, str>),
+    /// The segment from the last `}` to the closing quote.
+    InterpolationEnd(Cow<'a, str>),
     Number(f64),
+    /// The text of a `///` comment, attached by the parser to the
+    /// declaration that follows it.
+    DocComment(Cow<'a, str>),
+    // Trivia, only produced by `Lexer::lossless`
+    Whitespace(Cow<'a, str>),
+    Comment(Cow<'a, str>),
     Nil,
     // Operators
+    Arrow,
     Assign,
     Asterisk,
     Bang,
     Dot,
+    Ellipsis,
     Minus,
+    Percent,
     Plus,
+    Power,
+    Question,
+    QuestionDot,
+    QuestionQuestion,
     Slash,
+    // Compound assignment
+    AsteriskAssign,
+    MinusAssign,
+    PercentAssign,
+    PlusAssign,
+    SlashAssign,
     // Logic Operators
     LogicAnd,
     LogicOr,
@@ -27,6 +60,7 @@ pub enum Token {
     Equal,
     NotEqual,
     // Delimiters
+    Colon,
     Comma,
     Semicolon,
     // Parentheses
@@ -35,11 +69,17 @@ pub enum Token {
     // Braces
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
     // Keywords
+    Break,
+    Catch,
     Class,
+    Continue,
     Else,
     EOF,
     False,
+    Finally,
     For,
     Function,
     If,
@@ -47,16 +87,180 @@ pub enum Token {
     Return,
     Super,
     This,
+    Throw,
     True,
+    Try,
     While,
 }
 
-pub fn keyword(s: &str) -> Option<Token> {
+impl<'a> Token<'a> {
+    /// The same token without any borrows from the source, for keeping
+    /// around after the source is gone.
+    pub fn into_owned(self) -> Token<'static> {
+        match self {
+            Token::Illegal(err) => Token::Illegal(err),
+            Token::Ident(name) => Token::Ident(name),
+            Token::LoxString(text) => Token::LoxString(Cow::Owned(text.into_owned())),
+            Token::Interpolation(text) => Token::Interpolation(Cow::Owned(text.into_owned())),
+            Token::InterpolationMiddle(text) => {
+                Token::InterpolationMiddle(Cow::Owned(text.into_owned()))
+            }
+            Token::InterpolationEnd(text) => Token::InterpolationEnd(Cow::Owned(text.into_owned())),
+            Token::Number(n) => Token::Number(n),
+            Token::DocComment(text) => Token::DocComment(Cow::Owned(text.into_owned())),
+            Token::Whitespace(text) => Token::Whitespace(Cow::Owned(text.into_owned())),
+            Token::Comment(text) => Token::Comment(Cow::Owned(text.into_owned())),
+            Token::Nil => Token::Nil,
+            Token::Arrow => Token::Arrow,
+            Token::Assign => Token::Assign,
+            Token::Asterisk => Token::Asterisk,
+            Token::Bang => Token::Bang,
+            Token::Dot => Token::Dot,
+            Token::Ellipsis => Token::Ellipsis,
+            Token::Minus => Token::Minus,
+            Token::Percent => Token::Percent,
+            Token::Plus => Token::Plus,
+            Token::Power => Token::Power,
+            Token::Question => Token::Question,
+            Token::QuestionDot => Token::QuestionDot,
+            Token::QuestionQuestion => Token::QuestionQuestion,
+            Token::Slash => Token::Slash,
+            Token::AsteriskAssign => Token::AsteriskAssign,
+            Token::MinusAssign => Token::MinusAssign,
+            Token::PercentAssign => Token::PercentAssign,
+            Token::PlusAssign => Token::PlusAssign,
+            Token::SlashAssign => Token::SlashAssign,
+            Token::LogicAnd => Token::LogicAnd,
+            Token::LogicOr => Token::LogicOr,
+            Token::LessThan => Token::LessThan,
+            Token::LessEqual => Token::LessEqual,
+            Token::GreaterThan => Token::GreaterThan,
+            Token::GreaterEqual => Token::GreaterEqual,
+            Token::Equal => Token::Equal,
+            Token::NotEqual => Token::NotEqual,
+            Token::Colon => Token::Colon,
+            Token::Comma => Token::Comma,
+            Token::Semicolon => Token::Semicolon,
+            Token::LeftParentheses => Token::LeftParentheses,
+            Token::RightParentheses => Token::RightParentheses,
+            Token::LeftBrace => Token::LeftBrace,
+            Token::RightBrace => Token::RightBrace,
+            Token::LeftBracket => Token::LeftBracket,
+            Token::RightBracket => Token::RightBracket,
+            Token::Break => Token::Break,
+            Token::Catch => Token::Catch,
+            Token::Class => Token::Class,
+            Token::Continue => Token::Continue,
+            Token::Else => Token::Else,
+            Token::EOF => Token::EOF,
+            Token::False => Token::False,
+            Token::Finally => Token::Finally,
+            Token::For => Token::For,
+            Token::Function => Token::Function,
+            Token::If => Token::If,
+            Token::Let => Token::Let,
+            Token::Return => Token::Return,
+            Token::Super => Token::Super,
+            Token::This => Token::This,
+            Token::Throw => Token::Throw,
+            Token::True => Token::True,
+            Token::Try => Token::Try,
+            Token::While => Token::While,
+        }
+    }
+
+    /// Whitespace, comments and doc comments, the tokens the grammar never
+    /// sees. The parser still picks doc comments up for declarations.
+    pub fn is_trivia(&self) -> bool {
+        match self {
+            &Token::Whitespace(_) | &Token::Comment(_) | &Token::DocComment(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<'a> Display for Token<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            &Token::Illegal(ref err) => write!(f, "{}", err),
+            &Token::Ident(ref name) => write!(f, "{}", name),
+            &Token::LoxString(ref s) => write!(f, "\"{}\"", s),
+            &Token::Interpolation(ref s) => write!(f, "\"{}${{", s),
+            &Token::InterpolationMiddle(ref s) => write!(f, "}}{}${{", s),
+            &Token::InterpolationEnd(ref s) => write!(f, "}}{}\"", s),
+            &Token::Number(n) => write!(f, "{}", n),
+            &Token::DocComment(ref text) => write!(f, "/// {}", text),
+            &Token::Whitespace(ref text) | &Token::Comment(ref text) => write!(f, "{}", text),
+            &Token::Nil => write!(f, "nil"),
+            &Token::Arrow => write!(f, "=>"),
+            &Token::Assign => write!(f, "="),
+            &Token::Asterisk => write!(f, "*"),
+            &Token::Bang => write!(f, "!"),
+            &Token::Dot => write!(f, "."),
+            &Token::Ellipsis => write!(f, "..."),
+            &Token::Minus => write!(f, "-"),
+            &Token::Percent => write!(f, "%"),
+            &Token::Power => write!(f, "**"),
+            &Token::AsteriskAssign => write!(f, "*="),
+            &Token::MinusAssign => write!(f, "-="),
+            &Token::PercentAssign => write!(f, "%="),
+            &Token::PlusAssign => write!(f, "+="),
+            &Token::SlashAssign => write!(f, "/="),
+            &Token::Plus => write!(f, "+"),
+            &Token::Question => write!(f, "?"),
+            &Token::QuestionDot => write!(f, "?."),
+            &Token::QuestionQuestion => write!(f, "??"),
+            &Token::Slash => write!(f, "/"),
+            &Token::LogicAnd => write!(f, "and"),
+            &Token::LogicOr => write!(f, "or"),
+            &Token::LessThan => write!(f, "<"),
+            &Token::LessEqual => write!(f, "<="),
+            &Token::GreaterThan => write!(f, ">"),
+            &Token::GreaterEqual => write!(f, ">="),
+            &Token::Equal => write!(f, "=="),
+            &Token::NotEqual => write!(f, "!="),
+            &Token::Colon => write!(f, ":"),
+            &Token::Comma => write!(f, ","),
+            &Token::Semicolon => write!(f, ";"),
+            &Token::LeftParentheses => write!(f, "("),
+            &Token::RightParentheses => write!(f, ")"),
+            &Token::LeftBrace => write!(f, "{{"),
+            &Token::RightBrace => write!(f, "}}"),
+            &Token::LeftBracket => write!(f, "["),
+            &Token::RightBracket => write!(f, "]"),
+            &Token::Break => write!(f, "break"),
+            &Token::Catch => write!(f, "catch"),
+            &Token::Class => write!(f, "class"),
+            &Token::Continue => write!(f, "continue"),
+            &Token::Else => write!(f, "else"),
+            &Token::EOF => write!(f, "end of input"),
+            &Token::False => write!(f, "false"),
+            &Token::Finally => write!(f, "finally"),
+            &Token::For => write!(f, "for"),
+            &Token::Function => write!(f, "fn"),
+            &Token::If => write!(f, "if"),
+            &Token::Let => write!(f, "let"),
+            &Token::Return => write!(f, "return"),
+            &Token::Super => write!(f, "super"),
+            &Token::This => write!(f, "this"),
+            &Token::Throw => write!(f, "throw"),
+            &Token::True => write!(f, "true"),
+            &Token::Try => write!(f, "try"),
+            &Token::While => write!(f, "while"),
+        }
+    }
+}
+
+pub fn keyword(s: &str) -> Option<Token<'static>> {
     match s {
         "and" => Some(Token::LogicAnd),
+        "break" => Some(Token::Break),
+        "catch" => Some(Token::Catch),
         "class" => Some(Token::Class),
+        "continue" => Some(Token::Continue),
         "else" => Some(Token::Else),
         "false" => Some(Token::False),
+        "finally" => Some(Token::Finally),
         "fn" => Some(Token::Function),
         "for" => Some(Token::For),
         "if" => Some(Token::If),
@@ -66,8 +270,61 @@ pub fn keyword(s: &str) -> Option<Token> {
         "return" => Some(Token::Return),
         "super" => Some(Token::Super),
         "this" => Some(Token::This),
+        "throw" => Some(Token::Throw),
         "true" => Some(Token::True),
+        "try" => Some(Token::Try),
         "while" => Some(Token::While),
         &_ => None,
     }
 }
+
+/// A region of source text. `start` and `end` are byte offsets into the
+/// source, `line` and `column` (both 1-based) locate `start` for humans and
+/// `end_line` and `end_column` locate `end`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Span {
+    /// A span starting where `self` starts and ending where `other` ends.
+    pub fn to(&self, other: Span) -> Span {
+        Span {
+            start: self.start,
+            end: other.end,
+            line: self.line,
+            column: self.column,
+            end_line: other.end_line,
+            end_column: other.end_column,
+        }
+    }
+
+    /// An empty span where `self` ends.
+    pub fn after(&self) -> Span {
+        Span {
+            start: self.end,
+            end: self.end,
+            line: self.end_line,
+            column: self.end_column,
+            end_line: self.end_line,
+            end_column: self.end_column,
+        }
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpannedToken<'a> {
+    pub token: Token<'a>,
+    pub span: Span,
+}
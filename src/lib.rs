@@ -1,6 +1,10 @@
 pub mod ast_printer;
 pub mod ast;
+pub mod catalog;
+pub mod cst;
+pub mod diagnostics;
 pub mod interpreter;
 pub mod lexer;
 pub mod parser;
 pub mod resolver;
+pub mod symbol;
@@ -2,6 +2,7 @@ extern crate catlox;
 extern crate liner;
 
 use std::io;
+use std::io::IsTerminal;
 use std::env;
 use std::fs::File;
 use std::io::prelude::*;
@@ -9,6 +10,8 @@ use std::io::prelude::*;
 use liner::Context;
 
 use catlox::ast_printer::*;
+use catlox::catalog;
+use catlox::diagnostics::*;
 use catlox::lexer::*;
 use catlox::parser::*;
 use catlox::interpreter::*;
@@ -17,6 +20,7 @@ use catlox::resolver::*;
 fn main() {
     let args: Vec<String> = env::args().collect();
     let is_debug = args.contains(&String::from("debug"));
+    catalog::set_spicy(args.contains(&String::from("spicy")));
     let files : Vec<&String> = args.iter().filter(|s| s.ends_with(".cbox")).collect();
 
     if files.len() > 2 {
@@ -29,7 +33,7 @@ fn main() {
         f.read_to_string(&mut contents)
             .expect("something went wrong reading the file");
         let mut interpreter = Interpreter::new(Box::new(|s| println!("{}", s)));
-        run(&contents, is_debug, 0, &mut interpreter);
+        run(files[0], &contents, is_debug, 0, &mut interpreter);
     } else {
         repl(is_debug).unwrap();
     }
@@ -61,7 +65,9 @@ catlox is free software with ABSOLUTELY NO WARRANTY.
 
         match res {
             Ok(res) => {
-                if let Some(new_parse_seed) = run(&res, is_debug, parse_seed, &mut interpreter) {
+                if let Some(new_parse_seed) =
+                    run("<repl>", &res, is_debug, parse_seed, &mut interpreter)
+                {
                     parse_seed = new_parse_seed;
                 }
 
@@ -83,13 +89,20 @@ catlox is free software with ABSOLUTELY NO WARRANTY.
     Ok(())
 }
 
-fn run(res: &str, is_debug: bool, parse_seed: usize, interpreter: &mut Interpreter) -> Option<usize> {
-    let tokens: Vec<Token> = Lexer::new(res).collect();
+fn run(
+    file_name: &str,
+    res: &str,
+    is_debug: bool,
+    parse_seed: usize,
+    interpreter: &mut Interpreter,
+) -> Option<usize> {
+    let renderer = Renderer::new(file_name, res, io::stdout().is_terminal());
+    let tokens: Vec<SpannedToken> = Lexer::new(res).collect();
 
     if is_debug {
         println!("Tokens ----");
         for t in &tokens {
-            println!("{:?}", t);
+            println!("{:?} {}", t.token, t.span);
         }
     }
 
@@ -110,15 +123,17 @@ fn run(res: &str, is_debug: bool, parse_seed: usize, interpreter: &mut Interpret
             match resolve(&statements, interpreter) {
                 Ok(()) => match interpreter.interpret(&statements) {
                     Ok(_) => (),
-                    Err(err) => println!("Interpreter Error: {}", err),
+                    Err(err) => print!("{}", renderer.render(&Diagnostic::from(&err))),
                 },
-                Err(err) => println!("Resolver Error: {}", err),
+                Err(err) => print!("{}", renderer.render(&Diagnostic::from(&err))),
             }
 
             Some(new_parse_seed)
         }
-        Err(err) => {
-            println!("Parse Error: {}", err);
+        Err(errs) => {
+            for err in &errs {
+                print!("{}", renderer.render(&Diagnostic::from(err)));
+            }
             None
         }
     }
@@ -1,92 +1,280 @@
 use ast::*;
+use cst::SyntaxKind;
 use lexer::*;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use super::error::{Expected, ParseError};
+use super::event::Event;
 
 pub struct Parser<'a> {
-    tokens: &'a [Token],
+    tokens: Vec<&'a SpannedToken<'a>>,
+    /// Doc comments keyed by the index of the token they precede.
+    docs: HashMap<usize, String>,
     index: Cell<usize>,
     id: Cell<usize>,
+    /// Only recorded when building a syntax tree.
+    events: Option<RefCell<Vec<Event>>>,
 }
 
 impl<'a> Parser<'a> {
-    pub fn new(input: &'a [Token], parse_seed: usize) -> Parser {
+    pub fn new(input: &'a [SpannedToken<'a>], parse_seed: usize) -> Parser<'a> {
+        // Doc comments are set aside so the grammar never has to deal with
+        // them, declarations pick them up again by token index. Trivia from
+        // a lossless lexer is dropped altogether.
+        let mut tokens = vec![];
+        let mut docs: HashMap<usize, String> = HashMap::new();
+        for t in input {
+            match t.token {
+                Token::DocComment(ref text) => {
+                    let doc = docs.entry(tokens.len()).or_insert_with(String::new);
+                    if !doc.is_empty() {
+                        doc.push('\n');
+                    }
+                    doc.push_str(text);
+                }
+                ref token if token.is_trivia() => (),
+                _ => tokens.push(t),
+            }
+        }
+
         return Parser {
-            tokens: input,
+            tokens,
+            docs,
             index: Cell::new(0),
             id: Cell::new(parse_seed),
+            events: None,
         };
     }
 
-    pub fn parse(&mut self) -> Result<(usize, Vec<Statement>), String> {
+    pub fn parse(&mut self) -> Result<(usize, Vec<Statement>), Vec<ParseError>> {
+        // Anything the lexer couldn't make sense of would only confuse the
+        // parser, report those problems on their own.
+        let lexical_errors: Vec<ParseError> = self.tokens
+            .iter()
+            .filter_map(|t| match t.token {
+                Token::Illegal(ref error) => Some(ParseError::Lexical {
+                    error: error.clone(),
+                    span: t.span,
+                }),
+                _ => None,
+            })
+            .collect();
+        if !lexical_errors.is_empty() {
+            return Err(lexical_errors);
+        }
+
+        let (statements, errs) = self.declarations();
+        if !errs.is_empty() {
+            Err(errs)
+        } else {
+            Ok((self.id.get(), statements))
+        }
+    }
+
+    /// Run the grammar over all of the input, lexical errors or not, and
+    /// return the events a syntax tree is built from.
+    pub fn events(mut self) -> Vec<Event> {
+        self.events = Some(RefCell::new(vec![]));
+        self.declarations();
+        self.events.unwrap().into_inner()
+    }
+
+    /// Parse declarations until the input runs out. A failed one, together
+    /// with the tokens skipped to recover from it, goes in an `Error` node.
+    fn declarations(&self) -> (Vec<Statement>, Vec<ParseError>) {
         let mut statements: Vec<Statement> = vec![];
-        let mut errs: Vec<&'static str> = vec![];
+        let mut errs: Vec<ParseError> = vec![];
         while let Some(_) = self.peek() {
+            let checkpoint = self.checkpoint();
             match self.declaration() {
                 Ok(statement) => statements.push(statement),
                 Err(err) => {
                     errs.push(err);
-                    self.synchronize();
+                    self.precede(checkpoint, SyntaxKind::Error, || self.synchronize());
                 }
             }
         }
+        (statements, errs)
+    }
 
-        if !errs.is_empty() {
-            Err(errs.join("\n"))
-        } else {
-            Ok((self.id.get(), statements))
+    fn record<F: FnOnce(&mut Vec<Event>)>(&self, record: F) {
+        if let Some(ref events) = self.events {
+            record(&mut events.borrow_mut());
         }
     }
 
+    /// Where a node starting at the next token would begin, for `precede`.
+    fn checkpoint(&self) -> usize {
+        self.events
+            .as_ref()
+            .map_or(0, |events| events.borrow().len())
+    }
+
+    /// Run `parse` inside a node of `kind` starting at `checkpoint`, which
+    /// lets a node take in what was parsed before it was known to be needed,
+    /// like the left operand of a binary expression.
+    fn precede<T, F: FnOnce() -> T>(&self, checkpoint: usize, kind: SyntaxKind, parse: F) -> T {
+        self.record(|events| events.insert(checkpoint, Event::Start(kind)));
+        let result = parse();
+        self.record(|events| events.push(Event::Finish));
+        result
+    }
+
+    /// Run `parse` inside a node of `kind` starting at the next token.
+    fn node<T, F: FnOnce() -> T>(&self, kind: SyntaxKind, parse: F) -> T {
+        self.precede(self.checkpoint(), kind, parse)
+    }
+
+    /// The doc comment directly before the next token.
+    fn doc(&self) -> Option<String> {
+        self.docs.get(&self.index.get()).cloned()
+    }
+
     fn new_id(&self) -> usize {
         let new_id = self.id.get();
         self.id.set(new_id + 1);
         new_id
     }
 
-    fn advance(&self) -> Option<&Token> {
+    fn advance(&self) -> Option<&Token<'a>> {
         let previous_index = self.index.get();
         if previous_index >= self.tokens.len() {
             None
         } else {
             self.index.set(previous_index + 1);
-            Some(&self.tokens[previous_index])
+            self.record(|events| events.push(Event::Token));
+            Some(&self.tokens[previous_index].token)
         }
     }
 
-    fn peek(&self) -> Option<&Token> {
+    fn peek(&self) -> Option<&Token<'a>> {
         let index = self.index.get();
         if index >= self.tokens.len() {
             None
         } else {
-            Some(&self.tokens[index])
+            Some(&self.tokens[index].token)
         }
     }
 
-    fn previous(&self) -> Option<&Token> {
+    fn peek_next(&self) -> Option<&Token<'a>> {
+        self.tokens.get(self.index.get() + 1).map(|t| &t.token)
+    }
+
+    fn previous(&self) -> Option<&Token<'a>> {
         let index = self.index.get();
-        if index - 1 >= self.tokens.len() || index == 0 {
+        if index == 0 || index - 1 >= self.tokens.len() {
             None
         } else {
-            Some(&self.tokens[index - 1])
+            Some(&self.tokens[index - 1].token)
         }
     }
 
-    fn synchronize(&self) -> () {
-        self.advance();
+    /// The span of the next token, or an empty span at the end of the input.
+    fn peek_span(&self) -> Span {
+        match self.tokens.get(self.index.get()) {
+            Some(t) => t.span,
+            None => self.end_span(),
+        }
+    }
 
-        while let Some(next_token) = self.peek() {
-            if let Some(previous_token) = self.previous() {
-                if *previous_token == Token::Semicolon {
-                    return;
-                }
+    /// The span of the last consumed token.
+    fn previous_span(&self) -> Span {
+        let index = self.index.get();
+        if index == 0 || index > self.tokens.len() {
+            self.peek_span()
+        } else {
+            self.tokens[index - 1].span
+        }
+    }
+
+    fn end_span(&self) -> Span {
+        match self.tokens.last() {
+            Some(t) => t.span.after(),
+            None => Span {
+                line: 1,
+                column: 1,
+                end_line: 1,
+                end_column: 1,
+                ..Span::default()
+            },
+        }
+    }
+
+    /// The span from `start` up to and including the last consumed token.
+    fn span_from(&self, start: Span) -> Span {
+        start.to(self.previous_span())
+    }
+
+    /// Consume the next token if it is `expected`, otherwise report what was found instead.
+    fn consume(&self, expected: Token<'static>) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(t) if *t == expected => {
+                self.advance();
+                Ok(())
             }
+            found => Err(ParseError::UnexpectedToken {
+                expected: vec![Expected::Token(expected)],
+                found: found.cloned().map(Token::into_owned),
+                span: self.peek_span(),
+            }),
+        }
+    }
+
+    fn consume_identifier(&self) -> Result<Token<'static>, ParseError> {
+        match self.peek() {
+            Some(t @ &Token::Ident(_)) => {
+                self.advance();
+                Ok(t.clone().into_owned())
+            }
+            found => Err(ParseError::UnexpectedToken {
+                expected: vec![Expected::Identifier],
+                found: found.cloned().map(Token::into_owned),
+                span: self.peek_span(),
+            }),
+        }
+    }
+
+    fn consume_semicolon(&self) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(&Token::Semicolon) => {
+                self.advance();
+                Ok(())
+            }
+            // Point where the semicolon belongs rather than at whatever
+            // follows, which is often on the next line.
+            found => Err(ParseError::MissingSemicolon {
+                found: found.cloned().map(Token::into_owned),
+                span: self.previous_span().after(),
+            }),
+        }
+    }
 
+    /// Skip tokens until something that looks like the start of a statement.
+    ///
+    /// Errors are reported at the offending token without consuming it, so
+    /// the token under the cursor may already be a good place to resume.
+    fn synchronize(&self) -> () {
+        while let Some(next_token) = self.peek() {
             match next_token {
-                &Token::Function | &Token::If | &Token::Return | &Token::Let => return,
+                &Token::Break
+                | &Token::Class
+                | &Token::Continue
+                | &Token::For
+                | &Token::Function
+                | &Token::If
+                | &Token::Let
+                | &Token::Return
+                | &Token::Throw
+                | &Token::Try
+                | &Token::While => return,
                 _ => (),
             }
 
             self.advance();
+
+            if self.previous() == Some(&Token::Semicolon) {
+                return;
+            }
         }
     }
 
@@ -94,72 +282,148 @@ impl<'a> Parser<'a> {
 
     // Helpers ====
 
-    fn block(&self) -> Result<Vec<Statement>, &'static str> {
-        let mut statements: Vec<Statement> = Vec::new();
-        while match self.peek() {
-            Some(&Token::RightBrace) => false,
-            _ => true,
-        } {
-            statements.push(self.declaration()?);
+    /// Parse a parameter list up to and including the closing parenthesis,
+    /// the opening one has already been consumed.
+    fn parameters(&self) -> Result<Vec<Parameter>, ParseError> {
+        let mut parameters: Vec<Parameter> = Vec::new();
+        if self.peek() != Some(&Token::RightParentheses) {
+            loop {
+                let start = self.peek_span();
+                let rest = self.peek() == Some(&Token::Ellipsis);
+                parameters.push(self.node(SyntaxKind::Parameter, || {
+                    if rest {
+                        self.advance();
+                    }
+                    let name = self.consume_identifier()?;
+                    let default = match self.peek() {
+                        Some(&Token::Assign) if !rest => {
+                            self.advance();
+                            Some(self.expression()?)
+                        }
+                        _ => None,
+                    };
+                    Ok(Parameter {
+                        name,
+                        rest,
+                        default,
+                    })
+                })?);
+                match self.peek() {
+                    Some(&Token::Comma) if rest => {
+                        return Err(ParseError::RestParameterNotLast {
+                            span: self.span_from(start),
+                        })
+                    }
+                    Some(&Token::Comma) => {
+                        self.advance();
+                    }
+                    _ => break,
+                }
+            }
         }
+        self.consume(Token::RightParentheses)?;
+        Ok(parameters)
+    }
 
-        match self.advance() {
-            Some(&Token::RightBrace) => Ok(statements),
-            _ => Err("You didn't close your fucking block!"),
+    /// Whether the opening parenthesis just consumed starts the parameters
+    /// of an arrow function rather than a grouping.
+    /// A grouping is never followed by `=>`, so find the matching closing
+    /// parenthesis and look past it.
+    fn is_arrow_function(&self) -> bool {
+        let mut depth = 0;
+        let rest = &self.tokens[self.index.get()..];
+        for (i, t) in rest.iter().enumerate() {
+            match t.token {
+                Token::LeftParentheses | Token::LeftBracket | Token::LeftBrace => depth += 1,
+                Token::RightParentheses if depth == 0 => {
+                    return rest.get(i + 1).map(|t| &t.token) == Some(&Token::Arrow)
+                }
+                Token::RightParentheses | Token::RightBracket | Token::RightBrace => {
+                    if depth == 0 {
+                        return false;
+                    }
+                    depth -= 1;
+                }
+                _ => (),
+            }
         }
+        false
+    }
+
+    /// Parse a block, braces included.
+    fn block(&self) -> Result<Vec<Statement>, ParseError> {
+        self.node(SyntaxKind::Block, || {
+            self.consume(Token::LeftBrace)?;
+            let opened_at = self.previous_span();
+            let mut statements: Vec<Statement> = Vec::new();
+            while match self.peek() {
+                Some(&Token::RightBrace) | None => false,
+                _ => true,
+            } {
+                statements.push(self.declaration()?);
+            }
+
+            match self.advance() {
+                Some(&Token::RightBrace) => Ok(statements),
+                _ => Err(ParseError::UnclosedBlock {
+                    opened_at,
+                    span: self.peek_span(),
+                }),
+            }
+        })
     }
 
     // Parsing methods ====
 
-    fn declaration(&self) -> Result<Statement, &'static str> {
+    fn declaration(&self) -> Result<Statement, ParseError> {
+        let start = self.peek_span();
+        let doc = self.doc();
         match self.peek() {
-            Some(&Token::Class) => {
+            Some(&Token::Class) => self.node(SyntaxKind::Class, || {
                 self.advance();
-                self.class_declaration()
-            }
-            Some(&Token::Let) => {
-                self.advance();
-                self.var_declaration()
-            }
-            Some(&Token::Function) => {
+                self.class_declaration(start, doc)
+            }),
+            Some(&Token::Let) => self.node(SyntaxKind::Let, || {
                 self.advance();
-                self.function_declaration()
+                self.var_declaration(start, doc)
+            }),
+            // `fn (` starts an anonymous function, that's an expression.
+            Some(&Token::Function) if self.peek_next() != Some(&Token::LeftParentheses) => {
+                self.node(SyntaxKind::Function, || {
+                    self.advance();
+                    self.function_declaration(start, doc)
+                })
             }
             _ => self.statement(),
         }
     }
 
-    fn class_declaration(&self) -> Result<Statement, &'static str> {
-        let name = match self.advance() {
-            Some(t @ &Token::Ident(_)) => t.clone(),
-            _ => return Err("Classes need an identifier dumbass!"),
-        };
+    fn class_declaration(&self, start: Span, doc: Option<String>) -> Result<Statement, ParseError> {
+        let name = self.consume_identifier()?;
         let super_class = match self.peek() {
             Some(&Token::LessThan) => {
                 self.advance();
-                match self.advance() {
-                    Some(super_class_name @ &Token::Ident(_)) => Some(Expression::Variable {
-                        id: self.new_id(),
-                        name: super_class_name.clone(),
-                    }),
-                    _ => return Err("There should be a superclass name here! You fucking asshole!"),
-                }
+                let super_class_name =
+                    self.node(SyntaxKind::Variable, || self.consume_identifier())?;
+                Some(Expression::Variable {
+                    id: self.new_id(),
+                    span: self.previous_span(),
+                    name: super_class_name,
+                })
             }
             _ => None,
         };
-        match self.peek() {
-            Some(&Token::LeftBrace) => {
-                self.advance();
-            }
-            _ => return Err("There should be a left brace when defining a class! Dick."),
-        }
+        self.consume(Token::LeftBrace)?;
+        let opened_at = self.previous_span();
 
         let mut methods: Vec<Statement> = Vec::new();
         while match self.peek() {
             Some(&Token::RightBrace) | None => false,
             _ => true,
         } {
-            methods.push(self.function_declaration()?);
+            methods.push(self.node(SyntaxKind::Function, || {
+                self.function_declaration(self.peek_span(), self.doc())
+            })?);
         }
 
         match self.peek() {
@@ -169,185 +433,171 @@ impl<'a> Parser<'a> {
                     name,
                     methods,
                     super_class,
+                    doc,
+                    span: self.span_from(start),
                 })
             }
-            _ => Err("There should be a fucking right brace when defining a class!"),
+            _ => Err(ParseError::UnclosedBlock {
+                opened_at,
+                span: self.peek_span(),
+            }),
         }
     }
 
-    fn var_declaration(&self) -> Result<Statement, &'static str> {
-        match (self.advance(), self.peek()) {
-            (Some(token), Some(&Token::Assign)) => {
+    fn var_declaration(&self, start: Span, doc: Option<String>) -> Result<Statement, ParseError> {
+        let name = self.consume_identifier()?;
+        match self.peek() {
+            Some(&Token::Assign) => {
                 self.advance();
-                match (self.expression()?, self.peek()) {
-                    (e, Some(&Token::Semicolon)) => {
-                        self.advance();
-                        Ok(Statement::VariableDeclaration(token.clone(), Some(e)))
-                    }
-                    _ => Err(
-                        "OMG!!! It goes let whatever = some shit; How. Fucking. Hard. Is. That.",
-                    ),
-                }
+                let e = self.expression()?;
+                self.consume_semicolon()?;
+                Ok(Statement::VariableDeclaration(
+                    name,
+                    Some(e),
+                    doc,
+                    self.span_from(start),
+                ))
             }
-            (Some(token), Some(&Token::Semicolon)) => {
+            Some(&Token::Semicolon) => {
                 self.advance();
-                Ok(Statement::VariableDeclaration(token.clone(), None))
+                Ok(Statement::VariableDeclaration(
+                    name,
+                    None,
+                    doc,
+                    self.span_from(start),
+                ))
             }
-            _ => Err("Are you trying to write let a; and failing? Jesus."),
+            found => Err(ParseError::UnexpectedToken {
+                expected: vec![
+                    Expected::Token(Token::Assign),
+                    Expected::Token(Token::Semicolon),
+                ],
+                found: found.cloned().map(Token::into_owned),
+                span: self.peek_span(),
+            }),
         }
     }
 
-    fn function_declaration(&self) -> Result<Statement, &'static str> {
-        match (self.advance(), self.advance()) {
-            (Some(name @ &Token::Ident(_)), Some(&Token::LeftParentheses)) => {
-                let mut parameters: Vec<Token> = Vec::new();
-                if self.peek() != Some(&Token::RightParentheses) {
-                    loop {
-                        if parameters.len() >= 8 {
-                            return Err("Cannot have more than 8 parameters!");
-                        }
-                        if let Some(t @ &Token::Ident(_)) = self.advance() {
-                            parameters.push(t.clone());
-                        } else {
-                            return Err("That can't be used as a parameter");
-                        }
-                        match self.peek() {
-                            Some(&Token::Comma) => {
-                                self.advance();
-                            }
-                            _ => break,
-                        }
-                    }
-                }
-                if self.advance() != Some(&Token::RightParentheses) {
-                    return Err("You're going to need to close this function");
-                }
-                if self.advance() != Some(&Token::LeftBrace) {
-                    return Err("You're going to need a function body");
-                }
-                let statements = self.block()?;
-                Ok(Statement::FunctionDeclaration(
-                    name.clone(),
-                    parameters,
-                    statements,
-                ))
-            }
-            _ => Err("Yeah you said this was a function but it doesn't look like one."),
-        }
+    fn function_declaration(&self, start: Span, doc: Option<String>) -> Result<Statement, ParseError> {
+        let name = self.consume_identifier()?;
+        self.consume(Token::LeftParentheses)?;
+        let parameters = self.parameters()?;
+        let statements = self.block()?;
+        Ok(Statement::FunctionDeclaration(
+            name,
+            parameters,
+            statements,
+            doc,
+            self.span_from(start),
+        ))
     }
 
-    fn statement(&self) -> Result<Statement, &'static str> {
+    fn statement(&self) -> Result<Statement, ParseError> {
         match self.peek() {
-            Some(&Token::For) => {
+            Some(&Token::Break) => self.node(SyntaxKind::Break, || {
+                self.advance();
+                let start = self.previous_span();
+                self.consume_semicolon()?;
+                Ok(Statement::Break(self.span_from(start)))
+            }),
+            Some(&Token::Continue) => self.node(SyntaxKind::Continue, || {
+                self.advance();
+                let start = self.previous_span();
+                self.consume_semicolon()?;
+                Ok(Statement::Continue(self.span_from(start)))
+            }),
+            Some(&Token::For) => self.node(SyntaxKind::For, || {
                 self.advance();
                 self.for_statement()
-            }
-            Some(&Token::If) => {
+            }),
+            Some(&Token::If) => self.node(SyntaxKind::If, || {
                 self.advance();
                 self.if_statement()
-            }
-            Some(&Token::LeftBrace) => {
-                self.advance();
-                self.block_statement()
-            }
-            Some(&Token::Return) => {
+            }),
+            Some(&Token::LeftBrace) => self.block_statement(),
+            Some(&Token::Return) => self.node(SyntaxKind::Return, || {
                 self.advance();
                 self.return_statement()
-            }
-            Some(&Token::While) => {
+            }),
+            Some(&Token::Throw) => self.node(SyntaxKind::Throw, || {
+                self.advance();
+                let start = self.previous_span();
+                let value = self.expression()?;
+                self.consume_semicolon()?;
+                Ok(Statement::Throw(value, self.span_from(start)))
+            }),
+            Some(&Token::Try) => self.node(SyntaxKind::Try, || {
+                self.advance();
+                self.try_statement()
+            }),
+            Some(&Token::While) => self.node(SyntaxKind::While, || {
                 self.advance();
                 self.while_statement()
-            }
+            }),
             _ => self.expr_statement(),
         }
     }
 
-    fn for_statement(&self) -> Result<Statement, &'static str> {
-        match self.peek() {
-            Some(&Token::LeftParentheses) => {
-                self.advance();
-            }
-            _ => return Err("There should be a left parentheses after a for! Dick."),
-        }
+    fn for_statement(&self) -> Result<Statement, ParseError> {
+        let start = self.previous_span();
+        self.consume(Token::LeftParentheses)?;
 
         let initializer = match self.peek() {
             Some(&Token::Let) => {
+                let let_start = self.peek_span();
+                Some(self.node(SyntaxKind::Let, || {
+                    self.advance();
+                    self.var_declaration(let_start, None)
+                })?)
+            }
+            Some(&Token::Semicolon) => {
                 self.advance();
-                Some(self.var_declaration()?)
+                None
             }
-            Some(&Token::Semicolon) => None,
             _ => Some(self.expr_statement()?),
         };
 
         let condition = match self.peek() {
-            Some(&Token::Semicolon) => {
-                self.advance();
-                None
-            }
+            Some(&Token::Semicolon) => None,
             _ => Some(self.expression()?),
         };
 
-        match self.peek() {
-            Some(&Token::Semicolon) => {
-                self.advance();
-            }
-            _ => {
-                return Err(
-                    "Fucking for statements need fucking semicolons after fucking \
-                     conditions! Fuck!",
-                )
-            }
-        }
+        self.consume_semicolon()?;
 
         let increment = match self.peek() {
             Some(&Token::RightParentheses) => None,
             _ => Some(self.expression()?),
         };
 
-        match self.peek() {
-            Some(&Token::RightParentheses) => {
-                self.advance();
-            }
-            _ => return Err("Fucking for statements need a left parenthesis after shit! Fuck!"),
-        }
-
-        let mut body = self.statement()?;
+        self.consume(Token::RightParentheses)?;
 
-        if let Some(increment_inner) = increment {
-            body = Statement::Block(vec![body, Statement::Expression(increment_inner)]);
-        }
+        let body = self.statement()?;
+        let span = self.span_from(start);
 
-        body = match condition {
-            Some(condition_inner) => Statement::While(condition_inner, Box::new(body)),
-            None => Statement::While(
-                Expression::Literal {
-                    id: self.new_id(),
-                    token: Token::True,
-                },
-                Box::new(body),
-            ),
+        // The increment stays with the loop rather than being appended to
+        // the body, so that `continue` still runs it.
+        let condition = match condition {
+            Some(condition_inner) => condition_inner,
+            None => Expression::Literal {
+                id: self.new_id(),
+                span,
+                token: Token::True,
+            },
         };
+        let mut body = Statement::While(condition, Box::new(body), increment, span);
 
         if let Some(initializer_inner) = initializer {
-            body = Statement::Block(vec![initializer_inner, body]);
+            body = Statement::Block(vec![initializer_inner, body], span);
         }
 
         Ok(body)
     }
 
-    fn if_statement(&self) -> Result<Statement, &'static str> {
-        // Fail if there isn't a left parentheses
-        // TODO: Write a macro for this
-        match self.advance() {
-            Some(&Token::LeftParentheses) => (),
-            _ => return Err("You need a fucking left parentheses dummy."),
-        }
+    fn if_statement(&self) -> Result<Statement, ParseError> {
+        let start = self.previous_span();
+        self.consume(Token::LeftParentheses)?;
         let condition = self.expression()?;
-        // Fail if there isn't a right parentheses
-        match self.advance() {
-            Some(&Token::RightParentheses) => (),
-            _ => return Err("Conditionals have to be surrounded with parentheses dummy."),
-        }
+        self.consume(Token::RightParentheses)?;
 
         let then_branch = self.statement()?;
         match self.peek() {
@@ -358,120 +608,272 @@ impl<'a> Parser<'a> {
                     condition,
                     Box::new(then_branch),
                     Some(Box::new(else_branch)),
+                    self.span_from(start),
                 ))
             }
-            _ => Ok(Statement::If(condition, Box::new(then_branch), None)),
+            _ => Ok(Statement::If(
+                condition,
+                Box::new(then_branch),
+                None,
+                self.span_from(start),
+            )),
         }
     }
 
-    fn block_statement(&self) -> Result<Statement, &'static str> {
-        Ok(Statement::Block(self.block()?))
+    fn block_statement(&self) -> Result<Statement, ParseError> {
+        let start = self.peek_span();
+        Ok(Statement::Block(self.block()?, self.span_from(start)))
     }
 
-    fn return_statement(&self) -> Result<Statement, &'static str> {
+    fn return_statement(&self) -> Result<Statement, ParseError> {
+        let start = self.previous_span();
         match self.peek() {
             Some(&Token::Semicolon) => {
                 self.advance();
-                Ok(Statement::Return(None))
+                Ok(Statement::Return(None, self.span_from(start)))
             }
             _ => {
                 let expr = self.expression()?;
-
-                match self.peek() {
-                    Some(&Token::Semicolon) => {
-                        self.advance();
-                        Ok(Statement::Return(Some(expr)))
-                    }
-                    _ => Err("There should be a fucking semicolon after this expression!"),
-                }
+                self.consume_semicolon()?;
+                Ok(Statement::Return(Some(expr), self.span_from(start)))
             }
         }
     }
 
-    fn while_statement(&self) -> Result<Statement, &'static str> {
-        match self.peek() {
-            Some(&Token::LeftParentheses) => {
+    fn try_statement(&self) -> Result<Statement, ParseError> {
+        let start = self.previous_span();
+        let body = self.block()?;
+
+        let catch = match self.peek() {
+            Some(&Token::Catch) => {
                 self.advance();
+                self.consume(Token::LeftParentheses)?;
+                let name = self.consume_identifier()?;
+                self.consume(Token::RightParentheses)?;
+                Some((name, self.block()?))
             }
-            _ => return Err("There should be a left parentheses after the while! Dick."),
-        }
-
-        let condition = self.expression()?;
+            _ => None,
+        };
 
-        match self.peek() {
-            Some(&Token::RightParentheses) => {
+        let finally = match self.peek() {
+            Some(&Token::Finally) => {
                 self.advance();
+                Some(self.block()?)
             }
-            _ => return Err("There should be a right parentheses after the while! Dick."),
+            _ => None,
+        };
+
+        if catch.is_none() && finally.is_none() {
+            return Err(ParseError::UnexpectedToken {
+                expected: vec![
+                    Expected::Token(Token::Catch),
+                    Expected::Token(Token::Finally),
+                ],
+                found: self.peek().cloned().map(Token::into_owned),
+                span: self.peek_span(),
+            });
         }
 
+        Ok(Statement::Try {
+            body,
+            catch,
+            finally,
+            span: self.span_from(start),
+        })
+    }
+
+    fn while_statement(&self) -> Result<Statement, ParseError> {
+        let start = self.previous_span();
+        self.consume(Token::LeftParentheses)?;
+        let condition = self.expression()?;
+        self.consume(Token::RightParentheses)?;
+
         let body = self.statement()?;
 
-        Ok(Statement::While(condition, Box::new(body)))
+        Ok(Statement::While(
+            condition,
+            Box::new(body),
+            None,
+            self.span_from(start),
+        ))
     }
 
-    fn expr_statement(&self) -> Result<Statement, &'static str> {
-        let expr = self.expression()?;
-
-        match self.peek() {
-            Some(&Token::Semicolon) => {
-                self.advance();
-                Ok(Statement::Expression(expr))
-            }
-            _ => Err("There should be a fucking semicolon after this expression!"),
-        }
+    fn expr_statement(&self) -> Result<Statement, ParseError> {
+        self.node(SyntaxKind::ExpressionStatement, || {
+            let expr = self.expression()?;
+            self.consume_semicolon()?;
+            let span = expr.get_span().to(self.previous_span());
+            Ok(Statement::Expression(expr, span))
+        })
     }
 
     // Expressions =================================================================================
 
-    fn expression(&self) -> Result<Expression, &'static str> {
+    /// Expressions from the loosest binding to the tightest:
+    ///
+    /// | operators                    | associativity |
+    /// |------------------------------|---------------|
+    /// | `=` `+=` `-=` `*=` `/=` `%=` | right         |
+    /// | `? :`                        | right         |
+    /// | `??`                         | left          |
+    /// | `or`                         | left          |
+    /// | `and`                        | left          |
+    /// | `==` `!=`                    | left          |
+    /// | `<` `<=` `>` `>=`            | left          |
+    /// | `+` `-`                      | left          |
+    /// | `*` `/` `%`                  | left          |
+    /// | unary `!` `-`                | right         |
+    /// | `**`                         | right         |
+    /// | calls, `.`, `?.`, `[]`       | left          |
+    fn expression(&self) -> Result<Expression, ParseError> {
         self.assignment()
     }
 
-    fn assignment(&self) -> Result<Expression, &'static str> {
-        let expr = self.or()?;
+    fn assignment(&self) -> Result<Expression, ParseError> {
+        let checkpoint = self.checkpoint();
+        let expr = self.conditional()?;
+
+        let operator = match self.peek() {
+            Some(&Token::PlusAssign) => Some(Token::Plus),
+            Some(&Token::MinusAssign) => Some(Token::Minus),
+            Some(&Token::AsteriskAssign) => Some(Token::Asterisk),
+            Some(&Token::SlashAssign) => Some(Token::Slash),
+            Some(&Token::PercentAssign) => Some(Token::Percent),
+            _ => None,
+        };
+        if let Some(operator) = operator {
+            self.advance();
+            let value = self.precede(checkpoint, SyntaxKind::CompoundAssignment, || {
+                self.assignment()
+            })?;
+            return match expr {
+                Expression::Variable { .. }
+                | Expression::Get {
+                    optional: false, ..
+                }
+                | Expression::Index { .. } => Ok(Expression::CompoundAssignment {
+                    id: self.new_id(),
+                    span: expr.get_span().to(value.get_span()),
+                    target: Box::new(expr),
+                    operator,
+                    value: Box::new(value),
+                }),
+                _ => Err(ParseError::InvalidAssignmentTarget {
+                    span: expr.get_span(),
+                }),
+            };
+        }
 
         if let Some(_) = match self.peek() {
             Some(&Token::Assign) => self.advance(),
             _ => None,
         } {
-            let value = self.assignment()?;
+            let value = self.precede(checkpoint, SyntaxKind::Assignment, || self.assignment())?;
+            let span = expr.get_span().to(value.get_span());
 
             match expr {
                 Expression::Variable { name, .. } => {
                     return Ok(Expression::Assignment {
                         id: self.new_id(),
+                        span,
                         name: name,
                         expr: Box::new(value),
                     })
                 }
-                Expression::Get { name, object, .. } => {
+                Expression::Get {
+                    name,
+                    object,
+                    optional: false,
+                    ..
+                } => {
                     return Ok(Expression::Set {
                         id: self.new_id(),
+                        span,
                         name: name,
                         object: object,
                         value: Box::new(value),
                     })
                 }
-                _ => return Err("Are you trying to assign something? Get it the fuck right!"),
+                Expression::Index { object, index, .. } => {
+                    return Ok(Expression::SetIndex {
+                        id: self.new_id(),
+                        span,
+                        object,
+                        index,
+                        value: Box::new(value),
+                    })
+                }
+                _ => {
+                    return Err(ParseError::InvalidAssignmentTarget {
+                        span: expr.get_span(),
+                    })
+                }
             }
         }
 
         Ok(expr)
     }
 
-    fn or(&self) -> Result<Expression, &'static str> {
+    fn conditional(&self) -> Result<Expression, ParseError> {
+        let checkpoint = self.checkpoint();
+        let condition = self.coalesce()?;
+
+        if let Some(_) = match self.peek() {
+            Some(&Token::Question) => self.advance(),
+            _ => None,
+        } {
+            let (then_expr, else_expr) =
+                self.precede(checkpoint, SyntaxKind::Conditional, || {
+                    let then_expr = self.expression()?;
+                    self.consume(Token::Colon)?;
+                    Ok((then_expr, self.conditional()?))
+                })?;
+            return Ok(Expression::Conditional {
+                id: self.new_id(),
+                span: condition.get_span().to(else_expr.get_span()),
+                condition: Box::new(condition),
+                then_expr: Box::new(then_expr),
+                else_expr: Box::new(else_expr),
+            });
+        }
+
+        Ok(condition)
+    }
+
+    fn coalesce(&self) -> Result<Expression, ParseError> {
+        let checkpoint = self.checkpoint();
+        let mut expr = self.or()?;
+
+        while let Some(_) = match self.peek() {
+            Some(&Token::QuestionQuestion) => self.advance(),
+            _ => None,
+        } {
+            let right = self.precede(checkpoint, SyntaxKind::Coalesce, || self.or())?;
+            expr = Expression::Coalesce {
+                id: self.new_id(),
+                span: expr.get_span().to(right.get_span()),
+                l_expr: Box::new(expr),
+                r_expr: Box::new(right),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn or(&self) -> Result<Expression, ParseError> {
+        let checkpoint = self.checkpoint();
         let mut expr = self.and()?;
 
         while let Some(t) = match self.peek() {
             Some(&Token::LogicOr) => self.advance(),
             _ => None,
         } {
-            let right = self.and()?;
+            let right = self.precede(checkpoint, SyntaxKind::Logical, || self.and())?;
             expr = Expression::Logical {
                 id: self.new_id(),
+                span: expr.get_span().to(right.get_span()),
                 l_expr: Box::new(expr),
-                operator: t.clone(),
+                operator: t.clone().into_owned(),
                 r_expr: Box::new(right),
             };
         }
@@ -479,18 +881,20 @@ impl<'a> Parser<'a> {
         Ok(expr)
     }
 
-    fn and(&self) -> Result<Expression, &'static str> {
+    fn and(&self) -> Result<Expression, ParseError> {
+        let checkpoint = self.checkpoint();
         let mut expr = self.equality()?;
 
         while let Some(t) = match self.peek() {
             Some(&Token::LogicAnd) => self.advance(),
             _ => None,
         } {
-            let right = self.equality()?;
+            let right = self.precede(checkpoint, SyntaxKind::Logical, || self.equality())?;
             expr = Expression::Logical {
                 id: self.new_id(),
+                span: expr.get_span().to(right.get_span()),
                 l_expr: Box::new(expr),
-                operator: t.clone(),
+                operator: t.clone().into_owned(),
                 r_expr: Box::new(right),
             };
         }
@@ -498,18 +902,20 @@ impl<'a> Parser<'a> {
         Ok(expr)
     }
 
-    fn equality(&self) -> Result<Expression, &'static str> {
+    fn equality(&self) -> Result<Expression, ParseError> {
+        let checkpoint = self.checkpoint();
         let mut expr = self.comparison()?;
 
         while let Some(t) = match self.peek() {
             Some(&Token::Equal) | Some(&Token::NotEqual) => self.advance(),
             _ => None,
         } {
-            let right = self.comparison()?;
+            let right = self.precede(checkpoint, SyntaxKind::Binary, || self.comparison())?;
             expr = Expression::Binary {
                 id: self.new_id(),
+                span: expr.get_span().to(right.get_span()),
                 l_expr: Box::new(expr),
-                operator: t.clone(),
+                operator: t.clone().into_owned(),
                 r_expr: Box::new(right),
             };
         }
@@ -517,7 +923,8 @@ impl<'a> Parser<'a> {
         Ok(expr)
     }
 
-    fn comparison(&self) -> Result<Expression, &'static str> {
+    fn comparison(&self) -> Result<Expression, ParseError> {
+        let checkpoint = self.checkpoint();
         let mut expr = self.addition()?;
 
         while let Some(t) = match self.peek() {
@@ -528,11 +935,12 @@ impl<'a> Parser<'a> {
             | Some(&Token::LessEqual) => self.advance(),
             _ => None,
         } {
-            let right = self.addition()?;
+            let right = self.precede(checkpoint, SyntaxKind::Binary, || self.addition())?;
             expr = Expression::Binary {
                 id: self.new_id(),
+                span: expr.get_span().to(right.get_span()),
                 l_expr: Box::new(expr),
-                operator: t.clone(),
+                operator: t.clone().into_owned(),
                 r_expr: Box::new(right),
             };
         }
@@ -540,18 +948,20 @@ impl<'a> Parser<'a> {
         Ok(expr)
     }
 
-    fn addition(&self) -> Result<Expression, &'static str> {
+    fn addition(&self) -> Result<Expression, ParseError> {
+        let checkpoint = self.checkpoint();
         let mut expr = self.multiplication()?;
 
         while let Some(t) = match self.peek() {
             Some(&Token::Minus) | Some(&Token::Plus) => self.advance(),
             _ => None,
         } {
-            let right = self.multiplication()?;
+            let right = self.precede(checkpoint, SyntaxKind::Binary, || self.multiplication())?;
             expr = Expression::Binary {
                 id: self.new_id(),
+                span: expr.get_span().to(right.get_span()),
                 l_expr: Box::new(expr),
-                operator: t.clone(),
+                operator: t.clone().into_owned(),
                 r_expr: Box::new(right),
             };
         }
@@ -559,18 +969,20 @@ impl<'a> Parser<'a> {
         Ok(expr)
     }
 
-    fn multiplication(&self) -> Result<Expression, &'static str> {
+    fn multiplication(&self) -> Result<Expression, ParseError> {
+        let checkpoint = self.checkpoint();
         let mut expr = self.unary()?;
 
         while let Some(t) = match self.peek() {
-            Some(&Token::Slash) | Some(&Token::Asterisk) => self.advance(),
+            Some(&Token::Slash) | Some(&Token::Asterisk) | Some(&Token::Percent) => self.advance(),
             _ => None,
         } {
-            let right = self.unary()?;
+            let right = self.precede(checkpoint, SyntaxKind::Binary, || self.unary())?;
             expr = Expression::Binary {
                 id: self.new_id(),
+                span: expr.get_span().to(right.get_span()),
                 l_expr: Box::new(expr),
-                operator: t.clone(),
+                operator: t.clone().into_owned(),
                 r_expr: Box::new(right),
             };
         }
@@ -578,63 +990,131 @@ impl<'a> Parser<'a> {
         Ok(expr)
     }
 
-    fn unary(&self) -> Result<Expression, &'static str> {
+    fn unary(&self) -> Result<Expression, ParseError> {
         match self.peek() {
             Some(t) if *t == Token::Bang || *t == Token::Minus => {
-                self.advance();
-                let right = self.unary()?;
-                return Ok(Expression::Unary {
-                    id: self.new_id(),
-                    operator: t.clone(),
-                    expr: Box::new(right),
-                });
+                self.node(SyntaxKind::Unary, || {
+                    self.advance();
+                    let start = self.previous_span();
+                    let right = self.unary()?;
+                    Ok(Expression::Unary {
+                        id: self.new_id(),
+                        span: start.to(right.get_span()),
+                        operator: t.clone().into_owned(),
+                        expr: Box::new(right),
+                    })
+                })
             }
-            _ => self.call(),
+            _ => self.power(),
+        }
+    }
+
+    /// `**` binds tighter than unary minus on its left, `-2 ** 2` is `-4`,
+    /// and takes a unary on its right, which also makes it right associative.
+    fn power(&self) -> Result<Expression, ParseError> {
+        let checkpoint = self.checkpoint();
+        let expr = self.call()?;
+
+        if let Some(t) = match self.peek() {
+            Some(&Token::Power) => self.advance(),
+            _ => None,
+        } {
+            let right = self.precede(checkpoint, SyntaxKind::Binary, || self.unary())?;
+            return Ok(Expression::Binary {
+                id: self.new_id(),
+                span: expr.get_span().to(right.get_span()),
+                l_expr: Box::new(expr),
+                operator: t.clone().into_owned(),
+                r_expr: Box::new(right),
+            });
         }
+
+        Ok(expr)
     }
 
-    fn call(&self) -> Result<Expression, &'static str> {
+    fn call(&self) -> Result<Expression, ParseError> {
+        let checkpoint = self.checkpoint();
         let mut expr = self.primary()?;
 
-        while self.peek() == Some(&Token::LeftParentheses) || self.peek() == Some(&Token::Dot) {
-            match self.advance() {
+        while match self.peek() {
+            Some(&Token::LeftParentheses)
+            | Some(&Token::Dot)
+            | Some(&Token::QuestionDot)
+            | Some(&Token::LeftBracket) => true,
+            _ => false,
+        } {
+            expr = match self.advance() {
                 Some(&Token::LeftParentheses) => {
-                    let mut args: Vec<Expression> = Vec::new();
-                    if self.peek() != Some(&Token::RightParentheses) {
-                        loop {
-                            if args.len() >= 8 {
-                                return Err("More than 8 arguments are not allowed!");
-                            }
-                            args.push(self.expression()?);
-                            match self.peek() {
-                                Some(&Token::Comma) => {
-                                    self.advance();
+                    self.precede(checkpoint, SyntaxKind::Call, || {
+                        let mut args: Vec<Expression> = Vec::new();
+                        let mut named: Vec<(Token, Expression)> = Vec::new();
+                        if self.peek() != Some(&Token::RightParentheses) {
+                            loop {
+                                let is_named = match (self.peek(), self.peek_next()) {
+                                    (Some(&Token::Ident(_)), Some(&Token::Colon)) => true,
+                                    _ => false,
+                                };
+                                if is_named {
+                                    named.push(self.node(SyntaxKind::NamedArgument, || {
+                                        let name = self.consume_identifier()?;
+                                        self.advance();
+                                        Ok((name, self.expression()?))
+                                    })?);
+                                } else if !named.is_empty() {
+                                    return Err(ParseError::PositionalAfterNamed {
+                                        span: self.peek_span(),
+                                    });
+                                } else {
+                                    args.push(self.expression()?);
+                                }
+                                match self.peek() {
+                                    Some(&Token::Comma) => {
+                                        self.advance();
+                                    }
+                                    _ => break,
                                 }
-                                _ => break,
-                            }
-                        }
-                    }
-                    match self.advance() {
-                        Some(&Token::RightParentheses) => {
-                            expr = Expression::Call {
-                                id: self.new_id(),
-                                callee: Box::new(expr),
-                                arguments: args,
                             }
                         }
-                        _ => return Err("If you're trying to fucking call that try harder."),
-                    }
-                }
-                Some(&Token::Dot) => match self.advance() {
-                    Some(name @ &Token::Ident(_)) => {
-                        expr = Expression::Get {
+                        self.consume(Token::RightParentheses)?;
+                        Ok(Expression::Call {
                             id: self.new_id(),
-                            object: Box::new(expr),
-                            name: name.clone(),
-                        }
-                    }
-                    _ => return Err("There's supposed to be a property after '.'"),
-                },
+                            span: self.span_from(expr.get_span()),
+                            callee: Box::new(expr),
+                            arguments: args,
+                            named,
+                        })
+                    })?
+                }
+                Some(&Token::Dot) => self.precede(checkpoint, SyntaxKind::Get, || {
+                    let name = self.consume_identifier()?;
+                    Ok(Expression::Get {
+                        id: self.new_id(),
+                        span: self.span_from(expr.get_span()),
+                        object: Box::new(expr),
+                        name,
+                        optional: false,
+                    })
+                })?,
+                Some(&Token::QuestionDot) => self.precede(checkpoint, SyntaxKind::Get, || {
+                    let name = self.consume_identifier()?;
+                    Ok(Expression::Get {
+                        id: self.new_id(),
+                        span: self.span_from(expr.get_span()),
+                        object: Box::new(expr),
+                        name,
+                        optional: true,
+                    })
+                })?,
+                Some(&Token::LeftBracket) => self.precede(checkpoint, SyntaxKind::Index, || {
+                    let index = self.expression()?;
+                    self.consume(Token::RightBracket)?;
+                    Ok(Expression::Index {
+                        id: self.new_id(),
+                        span: self.span_from(expr.get_span()),
+                        object: Box::new(expr),
+                        index: Box::new(index),
+                    })
+                })?,
                 _ => unreachable!(),
             }
         }
@@ -642,43 +1122,186 @@ impl<'a> Parser<'a> {
         Ok(expr)
     }
 
-    fn primary(&self) -> Result<Expression, &'static str> {
+    fn primary(&self) -> Result<Expression, ParseError> {
+        let checkpoint = self.checkpoint();
         if let Some(t) = self.advance() {
+            let start = self.previous_span();
             match *t {
-                Token::LeftParentheses => {
-                    let expr = self.expression()?;
-                    match self.advance() {
-                        Some(&Token::RightParentheses) => Ok(Expression::Grouping {
+                Token::LeftParentheses if self.is_arrow_function() => {
+                    self.precede(checkpoint, SyntaxKind::Lambda, || {
+                        let parameters = self.parameters()?;
+                        self.consume(Token::Arrow)?;
+                        let body = match self.peek() {
+                            Some(&Token::LeftBrace) => self.block()?,
+                            _ => {
+                                let expr = self.expression()?;
+                                let span = expr.get_span();
+                                vec![Statement::Return(Some(expr), span)]
+                            }
+                        };
+                        Ok(Expression::Lambda {
                             id: self.new_id(),
-                            expr: Box::new(expr),
-                        }),
-                        _ => Err("There should be a fucking right parentheses here!"),
-                    }
+                            span: self.span_from(start),
+                            parameters,
+                            body,
+                        })
+                    })
                 }
-                Token::This => Ok(Expression::This { id: self.new_id() }),
+                Token::Function => self.precede(checkpoint, SyntaxKind::Lambda, || {
+                    self.consume(Token::LeftParentheses)?;
+                    let parameters = self.parameters()?;
+                    let body = self.block()?;
+                    Ok(Expression::Lambda {
+                        id: self.new_id(),
+                        span: self.span_from(start),
+                        parameters,
+                        body,
+                    })
+                }),
+                Token::LeftParentheses => self.precede(checkpoint, SyntaxKind::Grouping, || {
+                    let expr = self.expression()?;
+                    self.consume(Token::RightParentheses)?;
+                    Ok(Expression::Grouping {
+                        id: self.new_id(),
+                        span: self.span_from(start),
+                        expr: Box::new(expr),
+                    })
+                }),
+                Token::LeftBracket => self.precede(checkpoint, SyntaxKind::List, || {
+                    let mut elements = vec![];
+                    while self.peek() != Some(&Token::RightBracket) {
+                        elements.push(self.expression()?);
+                        match self.peek() {
+                            Some(&Token::Comma) => {
+                                self.advance();
+                            }
+                            _ => break,
+                        }
+                    }
+                    self.consume(Token::RightBracket)?;
+                    Ok(Expression::List {
+                        id: self.new_id(),
+                        span: self.span_from(start),
+                        elements,
+                    })
+                }),
+                // Statements starting with a brace are blocks, so this is
+                // always in expression position.
+                Token::LeftBrace => self.precede(checkpoint, SyntaxKind::Map, || {
+                    let mut entries = vec![];
+                    while self.peek() != Some(&Token::RightBrace) {
+                        entries.push(self.node(SyntaxKind::MapEntry, || {
+                            let key = self.expression()?;
+                            self.consume(Token::Colon)?;
+                            Ok((key, self.expression()?))
+                        })?);
+                        match self.peek() {
+                            Some(&Token::Comma) => {
+                                self.advance();
+                            }
+                            _ => break,
+                        }
+                    }
+                    self.consume(Token::RightBrace)?;
+                    Ok(Expression::Map {
+                        id: self.new_id(),
+                        span: self.span_from(start),
+                        entries,
+                    })
+                }),
+                Token::This => self.precede(checkpoint, SyntaxKind::This, || {
+                    Ok(Expression::This {
+                        id: self.new_id(),
+                        span: start,
+                    })
+                }),
                 Token::Number(_)
                 | Token::Nil
                 | Token::True
                 | Token::LoxString(_)
-                | Token::False => Ok(Expression::Literal {
-                    id: self.new_id(),
-                    token: t.clone(),
+                | Token::False => self.precede(checkpoint, SyntaxKind::Literal, || {
+                    Ok(Expression::Literal {
+                        id: self.new_id(),
+                        span: start,
+                        token: t.clone().into_owned(),
+                    })
                 }),
-                Token::Ident(_) => Ok(Expression::Variable {
-                    id: self.new_id(),
-                    name: t.clone(),
+                Token::Ident(_) => self.precede(checkpoint, SyntaxKind::Variable, || {
+                    Ok(Expression::Variable {
+                        id: self.new_id(),
+                        span: start,
+                        name: t.clone().into_owned(),
+                    })
                 }),
-                Token::Super => match (self.advance(), self.advance()) {
-                    (Some(&Token::Dot), Some(method @ &Token::Ident(_))) => Ok(Expression::Super {
+                Token::Interpolation(ref head) => {
+                    self.precede(checkpoint, SyntaxKind::Interpolation, || {
+                        self.interpolation(head, start)
+                    })
+                }
+                Token::Super => self.precede(checkpoint, SyntaxKind::Super, || {
+                    self.consume(Token::Dot)?;
+                    let method = self.consume_identifier()?;
+                    Ok(Expression::Super {
                         id: self.new_id(),
-                        method: method.clone(),
-                    }),
-                    _ => Err("There should be a a superclass method name"),
-                },
-                _ => Err("What the fuck is this shit!"),
+                        span: self.span_from(start),
+                        method,
+                    })
+                }),
+                _ => Err(ParseError::ExpectedExpression {
+                    found: Some(t.clone().into_owned()),
+                    span: start,
+                }),
             }
         } else {
-            Err("There should be some shit here!")
+            Err(ParseError::ExpectedExpression {
+                found: None,
+                span: self.peek_span(),
+            })
+        }
+    }
+
+    /// The opening segment has been consumed, alternate between embedded
+    /// expressions and the string segments following them.
+    fn interpolation(&self, head: &str, start: Span) -> Result<Expression, ParseError> {
+        let mut parts = vec![];
+        self.push_segment(&mut parts, head, start);
+        loop {
+            parts.push(self.expression()?);
+            let segment_span = self.peek_span();
+            match self.peek() {
+                Some(&Token::InterpolationMiddle(ref segment)) => {
+                    self.advance();
+                    self.push_segment(&mut parts, segment, segment_span);
+                }
+                Some(&Token::InterpolationEnd(ref segment)) => {
+                    self.advance();
+                    self.push_segment(&mut parts, segment, segment_span);
+                    break;
+                }
+                found => {
+                    return Err(ParseError::UnexpectedToken {
+                        expected: vec![Expected::Token(Token::RightBrace)],
+                        found: found.cloned().map(Token::into_owned),
+                        span: segment_span,
+                    })
+                }
+            }
+        }
+
+        Ok(Expression::Interpolation {
+            id: self.new_id(),
+            span: self.span_from(start),
+            parts,
+        })
+    }
+
+    fn push_segment(&self, parts: &mut Vec<Expression>, segment: &str, span: Span) {
+        if !segment.is_empty() {
+            parts.push(Expression::Literal {
+                id: self.new_id(),
+                span,
+                token: Token::LoxString(segment.to_string().into()),
+            });
         }
     }
 }
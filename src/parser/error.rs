@@ -0,0 +1,143 @@
+use catalog::ErrorCode;
+use lexer::{LexError, Span, Token};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Something the parser was looking for when it gave up.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expected {
+    Token(Token<'static>),
+    Identifier,
+    Expression,
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            &Expected::Token(ref token) => write!(f, "'{}'", token),
+            &Expected::Identifier => write!(f, "an identifier"),
+            &Expected::Expression => write!(f, "an expression"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParseError {
+    Lexical {
+        error: LexError,
+        span: Span,
+    },
+    UnexpectedToken {
+        expected: Vec<Expected>,
+        found: Option<Token<'static>>,
+        span: Span,
+    },
+    MissingSemicolon {
+        found: Option<Token<'static>>,
+        span: Span,
+    },
+    UnclosedBlock {
+        opened_at: Span,
+        span: Span,
+    },
+    RestParameterNotLast {
+        span: Span,
+    },
+    PositionalAfterNamed {
+        span: Span,
+    },
+    InvalidAssignmentTarget {
+        span: Span,
+    },
+    ExpectedExpression {
+        found: Option<Token<'static>>,
+        span: Span,
+    },
+}
+
+impl ParseError {
+    /// Where in the source the error was detected.
+    pub fn span(&self) -> Span {
+        match self {
+            &ParseError::Lexical { span, .. } => span,
+            &ParseError::UnexpectedToken { span, .. } => span,
+            &ParseError::MissingSemicolon { span, .. } => span,
+            &ParseError::UnclosedBlock { span, .. } => span,
+            &ParseError::RestParameterNotLast { span } => span,
+            &ParseError::PositionalAfterNamed { span } => span,
+            &ParseError::InvalidAssignmentTarget { span } => span,
+            &ParseError::ExpectedExpression { span, .. } => span,
+        }
+    }
+
+    /// The tokens (or kinds of tokens) that would have been accepted.
+    pub fn expected(&self) -> Vec<Expected> {
+        match self {
+            &ParseError::UnexpectedToken { ref expected, .. } => expected.clone(),
+            &ParseError::MissingSemicolon { .. } => vec![Expected::Token(Token::Semicolon)],
+            &ParseError::UnclosedBlock { .. } => vec![Expected::Token(Token::RightBrace)],
+            &ParseError::ExpectedExpression { .. } => vec![Expected::Expression],
+            _ => vec![],
+        }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            &ParseError::Lexical { ref error, .. } => error.code(),
+            &ParseError::UnexpectedToken { .. } => ErrorCode::UnexpectedToken,
+            &ParseError::MissingSemicolon { .. } => ErrorCode::MissingSemicolon,
+            &ParseError::UnclosedBlock { .. } => ErrorCode::UnclosedBlock,
+            &ParseError::RestParameterNotLast { .. } => ErrorCode::RestParameterNotLast,
+            &ParseError::PositionalAfterNamed { .. } => ErrorCode::PositionalAfterNamed,
+            &ParseError::InvalidAssignmentTarget { .. } => ErrorCode::InvalidAssignmentTarget,
+            &ParseError::ExpectedExpression { .. } => ErrorCode::ExpectedExpression,
+        }
+    }
+
+    /// The token actually found, `None` at the end of the input.
+    pub fn found(&self) -> Option<&Token<'static>> {
+        match self {
+            &ParseError::UnexpectedToken { ref found, .. }
+            | &ParseError::MissingSemicolon { ref found, .. }
+            | &ParseError::ExpectedExpression { ref found, .. } => found.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let message = match self {
+            &ParseError::Lexical { ref error, .. } => error.to_string(),
+            &ParseError::UnexpectedToken {
+                ref expected,
+                ref found,
+                ..
+            } => self.code().message(&[
+                &expected
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" or "),
+                &describe(found),
+            ]),
+            &ParseError::MissingSemicolon { ref found, .. }
+            | &ParseError::ExpectedExpression { ref found, .. } => {
+                self.code().message(&[&describe(found)])
+            }
+            &ParseError::UnclosedBlock { opened_at, .. } => {
+                self.code().message(&[&opened_at.to_string()])
+            }
+            &ParseError::InvalidAssignmentTarget { .. }
+            | &ParseError::RestParameterNotLast { .. }
+            | &ParseError::PositionalAfterNamed { .. } => self.code().message(&[]),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+fn describe(found: &Option<Token>) -> String {
+    match found {
+        &Some(ref token) => format!("'{}'", token),
+        &None => String::from("end of input"),
+    }
+}
@@ -0,0 +1,11 @@
+use cst::SyntaxKind;
+
+/// What the parser did, in order. Replaying the events over the lossless
+/// tokens gives the syntax tree, trivia goes in between the tokens.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Event {
+    Start(SyntaxKind),
+    /// The next token that isn't trivia was consumed.
+    Token,
+    Finish,
+}
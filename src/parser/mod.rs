@@ -1,3 +1,7 @@
 pub use self::core::Parser;
+pub use self::error::{Expected, ParseError};
+pub use self::event::Event;
 
 mod core;
+mod error;
+mod event;
@@ -1,7 +1,10 @@
 use ast::*;
+use catalog::ErrorCode;
 use interpreter::Interpreter;
 use std::collections::HashMap;
-use lexer::Token;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use lexer::{Span, Token};
+use symbol::Symbol;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 enum FunctionType {
@@ -18,47 +21,69 @@ enum ClassType {
     SubClass,
 }
 
-pub fn resolve(stmts: &[Statement], interpreter: &mut Interpreter) -> Result<(), String> {
+pub fn resolve(stmts: &[Statement], interpreter: &mut Interpreter) -> Result<(), ResolverError> {
     let mut resolver = Resolver {
         interpreter: interpreter,
         scopes: Vec::new(),
         function_type: FunctionType::None,
         class_type: ClassType::None,
+        loop_depth: 0,
     };
     resolver.resolve(stmts)?;
 
     Ok(())
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub struct ResolverError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub span: Span,
+}
+
+impl Display for ResolverError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}: {}", self.span, self.message)
+    }
+}
+
+fn error(span: Span, code: ErrorCode, args: &[&str]) -> ResolverError {
+    ResolverError {
+        code,
+        message: code.message(args),
+        span,
+    }
+}
+
 struct Resolver<'a> {
     interpreter: &'a mut Interpreter,
-    scopes: Vec<HashMap<String, bool>>,
+    scopes: Vec<HashMap<Symbol, bool>>,
     function_type: FunctionType,
     class_type: ClassType,
+    /// How many loops enclose the current statement within this function.
+    loop_depth: usize,
 }
 
 impl<'a> Resolver<'a> {
-    fn resolve(&mut self, stmts: &[Statement]) -> Result<(), String> {
+    fn resolve(&mut self, stmts: &[Statement]) -> Result<(), ResolverError> {
         for stmt in stmts {
             self.visit_statement(stmt)?;
         }
         Ok(())
     }
 
-    fn declare(&mut self, name_token: &Token) -> Result<(), String> {
+    fn declare(&mut self, name_token: &Token, span: Span) -> Result<(), ResolverError> {
         if self.scopes.is_empty() {
             return Ok(());
         }
 
         match name_token {
-            &Token::Ident(ref name) => {
+            &Token::Ident(name) => {
                 let len = self.scopes.len() - 1;
-                if self.scopes[len].contains_key(name) {
-                    return Err(String::from(
-                        "Variable with this name already declared in this scope",
-                    ));
+                if self.scopes[len].contains_key(&name) {
+                    return Err(error(span, ErrorCode::AlreadyDeclared, &[name.as_str()]));
                 }
-                self.scopes[len].insert(name.to_string(), false);
+                self.scopes[len].insert(name, false);
             }
             _ => unreachable!(),
         };
@@ -71,12 +96,12 @@ impl<'a> Resolver<'a> {
         }
 
         match name_token {
-            &Token::Ident(ref name) => {
+            &Token::Ident(name) => {
                 if self.scopes.is_empty() {
                     return;
                 }
                 let len = self.scopes.len() - 1;
-                self.scopes[len].insert(name.to_string(), true);
+                self.scopes[len].insert(name, true);
             }
             _ => unreachable!(),
         };
@@ -90,9 +115,9 @@ impl<'a> Resolver<'a> {
         self.scopes.pop();
     }
 
-    fn resolve_local(&mut self, name: &str, expr: &Expression) {
+    fn resolve_local(&mut self, name: Symbol, expr: &Expression) {
         for i in (0..self.scopes.len()).rev() {
-            if self.scopes[i].contains_key(name) {
+            if self.scopes[i].contains_key(&name) {
                 // Scopes does not include the global environment, the resolver
                 // will. Add one for this reason.
                 self.interpreter.resolve(expr, i + 1);
@@ -103,32 +128,37 @@ impl<'a> Resolver<'a> {
 
     fn resolve_fn(
         &mut self,
-        function_stmt: &Statement,
+        parameters: &[Parameter],
+        body: &[Statement],
+        span: Span,
         function_type: FunctionType,
-    ) -> Result<(), String> {
+    ) -> Result<(), ResolverError> {
         let enclosing_function = self.function_type.clone();
         self.function_type = function_type;
-        match function_stmt {
-            &Statement::FunctionDeclaration(_, ref parameters, ref body) => {
-                self.begin_scope();
-                for param in parameters {
-                    self.declare(param)?;
-                    self.define(param);
-                }
-                self.resolve(body)?;
-                self.end_scope();
+        // A loop around the function doesn't make break valid inside it.
+        let enclosing_loop_depth = self.loop_depth;
+        self.loop_depth = 0;
+        self.begin_scope();
+        for param in parameters {
+            // Defaults see the parameters before them.
+            if let Some(ref default) = param.default {
+                self.visit_expression(default)?;
             }
-            _ => unreachable!(),
-        };
+            self.declare(&param.name, span)?;
+            self.define(&param.name);
+        }
+        self.resolve(body)?;
+        self.end_scope();
 
         self.function_type = enclosing_function;
+        self.loop_depth = enclosing_loop_depth;
         Ok(())
     }
 }
 
 impl<'a> MutVisitor for Resolver<'a> {
-    type E = Result<(), String>;
-    type S = Result<(), String>;
+    type E = Result<(), ResolverError>;
+    type S = Result<(), ResolverError>;
 
     fn visit_expression(&mut self, e: &Expression) -> Self::E {
         match e {
@@ -137,7 +167,7 @@ impl<'a> MutVisitor for Resolver<'a> {
             } => {
                 self.visit_expression(expr)?;
                 let name = match name {
-                    &Token::Ident(ref name_s) => name_s,
+                    &Token::Ident(name) => name,
                     _ => unreachable!(),
                 };
                 self.resolve_local(name, e);
@@ -155,21 +185,86 @@ impl<'a> MutVisitor for Resolver<'a> {
             &Expression::Call {
                 ref callee,
                 ref arguments,
+                ref named,
                 ..
             } => {
                 self.visit_expression(callee)?;
                 for expr in arguments {
                     self.visit_expression(expr)?;
                 }
+                for &(_, ref expr) in named {
+                    self.visit_expression(expr)?;
+                }
+                Ok(())
+            }
+            // A variable target resolves like any read, the interpreter
+            // writes back through the same resolution.
+            &Expression::CompoundAssignment {
+                ref target,
+                ref value,
+                ..
+            } => {
+                self.visit_expression(target)?;
+                self.visit_expression(value)?;
+                Ok(())
+            }
+            &Expression::Conditional {
+                ref condition,
+                ref then_expr,
+                ref else_expr,
+                ..
+            } => {
+                self.visit_expression(condition)?;
+                self.visit_expression(then_expr)?;
+                self.visit_expression(else_expr)?;
                 Ok(())
             }
             &Expression::Get { ref object, .. } => self.visit_expression(object),
             &Expression::Grouping { ref expr, .. } => self.visit_expression(expr),
+            &Expression::Interpolation { ref parts, .. } => {
+                for expr in parts {
+                    self.visit_expression(expr)?;
+                }
+                Ok(())
+            }
+            &Expression::Index {
+                ref object,
+                ref index,
+                ..
+            } => {
+                self.visit_expression(object)?;
+                self.visit_expression(index)?;
+                Ok(())
+            }
+            &Expression::Lambda {
+                ref parameters,
+                ref body,
+                span,
+                ..
+            } => self.resolve_fn(parameters, body, span, FunctionType::Function),
+            &Expression::List { ref elements, .. } => {
+                for expr in elements {
+                    self.visit_expression(expr)?;
+                }
+                Ok(())
+            }
             &Expression::Literal { .. } => Ok(()),
+            &Expression::Map { ref entries, .. } => {
+                for &(ref key, ref value) in entries {
+                    self.visit_expression(key)?;
+                    self.visit_expression(value)?;
+                }
+                Ok(())
+            }
             &Expression::Logical {
                 ref l_expr,
                 ref r_expr,
                 ..
+            }
+            | &Expression::Coalesce {
+                ref l_expr,
+                ref r_expr,
+                ..
             } => {
                 self.visit_expression(l_expr)?;
                 self.visit_expression(r_expr)?;
@@ -184,40 +279,47 @@ impl<'a> MutVisitor for Resolver<'a> {
                 self.visit_expression(object)?;
                 Ok(())
             }
-            &Expression::Super { .. } => {
+            &Expression::SetIndex {
+                ref object,
+                ref index,
+                ref value,
+                ..
+            } => {
+                self.visit_expression(object)?;
+                self.visit_expression(index)?;
+                self.visit_expression(value)?;
+                Ok(())
+            }
+            &Expression::Super { span, .. } => {
                 if self.class_type == ClassType::None {
-                    return Err(String::from("Cannot use 'super' outside of a class."));
+                    return Err(error(span, ErrorCode::SuperOutsideClass, &[]));
                 } else if self.class_type != ClassType::SubClass {
-                    return Err(String::from(
-                        "Cannot use 'super' in a class with no superclass.",
-                    ));
+                    return Err(error(span, ErrorCode::SuperWithoutSuperclass, &[]));
                 }
 
-                self.resolve_local("super", e);
+                self.resolve_local(Symbol::SUPER, e);
                 Ok(())
             }
-            &Expression::This { .. } => {
-                if self.class_type != ClassType::Class {
-                    return Err(String::from("You can't use this outside of a class!"));
+            &Expression::This { span, .. } => {
+                if self.class_type == ClassType::None {
+                    return Err(error(span, ErrorCode::ThisOutsideClass, &[]));
                 }
-                self.resolve_local("this", e);
+                self.resolve_local(Symbol::THIS, e);
                 Ok(())
             }
             &Expression::Unary { ref expr, .. } => self.visit_expression(expr),
-            &Expression::Variable { ref name, .. } => {
+            &Expression::Variable { ref name, span, .. } => {
                 // We're in the global scope do nothing
                 if self.scopes.is_empty() {
                     return Ok(());
                 }
                 let name = match name {
-                    &Token::Ident(ref name_s) => name_s,
+                    &Token::Ident(name) => name,
                     _ => unreachable!(),
                 };
                 let len = self.scopes.len() - 1;
-                if self.scopes[len].get(name) == Some(&false) {
-                    return Err(String::from(
-                        "Cannot read local variable in its own initializer.",
-                    ));
+                if self.scopes[len].get(&name) == Some(&false) {
+                    return Err(error(span, ErrorCode::ReadInOwnInitializer, &[name.as_str()]));
                 }
                 self.resolve_local(name, e);
                 Ok(())
@@ -231,8 +333,10 @@ impl<'a> MutVisitor for Resolver<'a> {
                 ref name,
                 ref methods,
                 ref super_class,
+                span,
+                ..
             } => {
-                self.declare(name)?;
+                self.declare(name, span)?;
                 self.define(name);
 
                 let enclosing_class = self.class_type.clone();
@@ -242,27 +346,33 @@ impl<'a> MutVisitor for Resolver<'a> {
                     self.begin_scope();
                     self.class_type = ClassType::SubClass;
                     let len = self.scopes.len() - 1;
-                    self.scopes[len].insert(String::from("super"), true);
+                    self.scopes[len].insert(Symbol::SUPER, true);
                     self.visit_expression(super_class)?;
                 }
 
                 self.begin_scope();
                 // resolve this
                 let len = self.scopes.len() - 1;
-                self.scopes[len].insert(String::from("this"), true);
+                self.scopes[len].insert(Symbol::THIS, true);
 
                 for method in methods {
-                    let function_type = match method {
-                        &Statement::FunctionDeclaration(ref name, _, _) => match name {
-                            &Token::Ident(ref fn_name) => match fn_name.as_ref() {
-                                "init" => FunctionType::Initializer,
-                                _ => FunctionType::Method,
-                            },
-                            _ => unreachable!(),
-                        },
+                    match method {
+                        &Statement::FunctionDeclaration(
+                            ref name,
+                            ref parameters,
+                            ref body,
+                            _,
+                            span,
+                        ) => {
+                            let function_type = match name {
+                                &Token::Ident(Symbol::INIT) => FunctionType::Initializer,
+                                &Token::Ident(_) => FunctionType::Method,
+                                _ => unreachable!(),
+                            };
+                            self.resolve_fn(parameters, body, span, function_type)?;
+                        }
                         _ => unreachable!(),
-                    };
-                    self.resolve_fn(method, function_type)?;
+                    }
                 }
                 self.end_scope();
 
@@ -273,23 +383,35 @@ impl<'a> MutVisitor for Resolver<'a> {
                 self.class_type = enclosing_class;
                 Ok(())
             }
-            &Statement::Block(ref statements) => {
+            &Statement::Block(ref statements, _) => {
                 self.begin_scope();
                 self.resolve(statements)?;
                 self.end_scope();
                 Ok(())
             }
-            &Statement::Expression(ref expr) => {
+            &Statement::Break(span) => {
+                if self.loop_depth == 0 {
+                    return Err(error(span, ErrorCode::BreakOutsideLoop, &[]));
+                }
+                Ok(())
+            }
+            &Statement::Continue(span) => {
+                if self.loop_depth == 0 {
+                    return Err(error(span, ErrorCode::ContinueOutsideLoop, &[]));
+                }
+                Ok(())
+            }
+            &Statement::Expression(ref expr, _) => {
                 self.visit_expression(expr)?;
                 Ok(())
             }
-            &Statement::FunctionDeclaration(ref name, _, _) => {
-                self.declare(name)?;
+            &Statement::FunctionDeclaration(ref name, ref parameters, ref body, _, span) => {
+                self.declare(name, span)?;
                 self.define(name);
-                self.resolve_fn(s, FunctionType::Function)?;
+                self.resolve_fn(parameters, body, span, FunctionType::Function)?;
                 Ok(())
             }
-            &Statement::If(ref condition, ref then, ref else_option) => {
+            &Statement::If(ref condition, ref then, ref else_option, _) => {
                 self.visit_expression(condition)?;
                 self.visit_statement(then)?;
                 if let &Some(ref stmt) = else_option {
@@ -297,20 +419,48 @@ impl<'a> MutVisitor for Resolver<'a> {
                 }
                 Ok(())
             }
-            &Statement::Return(ref expr_option) => {
+            &Statement::Return(ref expr_option, span) => {
                 if self.function_type == FunctionType::None {
-                    return Err(String::from("Cannot return from top level code"));
+                    return Err(error(span, ErrorCode::ReturnFromTopLevel, &[]));
                 }
-                if self.function_type == FunctionType::Initializer {
-                    return Err(String::from("Cannot return from initializer"));
+                // A bare `return;` leaves an initializer early, it still
+                // returns the instance.
+                if self.function_type == FunctionType::Initializer && expr_option.is_some() {
+                    return Err(error(span, ErrorCode::ReturnFromInitializer, &[]));
                 }
                 if let &Some(ref expr) = expr_option {
                     self.visit_expression(expr)?;
                 }
                 Ok(())
             }
-            &Statement::VariableDeclaration(ref name, ref initializer) => {
-                self.declare(name)?;
+            &Statement::Throw(ref expr, _) => self.visit_expression(expr),
+            // The caught value lives in the same scope as the handler's
+            // own declarations.
+            &Statement::Try {
+                ref body,
+                ref catch,
+                ref finally,
+                span,
+            } => {
+                self.begin_scope();
+                self.resolve(body)?;
+                self.end_scope();
+                if let &Some((ref name, ref handler)) = catch {
+                    self.begin_scope();
+                    self.declare(name, span)?;
+                    self.define(name);
+                    self.resolve(handler)?;
+                    self.end_scope();
+                }
+                if let &Some(ref finally) = finally {
+                    self.begin_scope();
+                    self.resolve(finally)?;
+                    self.end_scope();
+                }
+                Ok(())
+            }
+            &Statement::VariableDeclaration(ref name, ref initializer, _, span) => {
+                self.declare(name, span)?;
                 match initializer {
                     &Some(ref expr) => self.visit_expression(expr)?,
                     _ => (),
@@ -319,9 +469,14 @@ impl<'a> MutVisitor for Resolver<'a> {
 
                 Ok(())
             }
-            &Statement::While(ref condition, ref body) => {
+            &Statement::While(ref condition, ref body, ref increment, _) => {
                 self.visit_expression(condition)?;
+                self.loop_depth += 1;
                 self.visit_statement(body)?;
+                self.loop_depth -= 1;
+                if let &Some(ref increment) = increment {
+                    self.visit_expression(increment)?;
+                }
                 Ok(())
             }
         }
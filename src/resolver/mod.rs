@@ -1,3 +1,3 @@
-pub use self::core::resolve;
+pub use self::core::{resolve, ResolverError};
 
 mod core;
@@ -0,0 +1,91 @@
+//! Interned names. The lexer stores every identifier once in a global table
+//! and hands out `Symbol`s, small integers that are cheap to copy, hash and
+//! compare, so scopes, environments, fields and methods never hash strings.
+//!
+//! Interned strings live for the rest of the program, there are only as
+//! many of them as distinct names in the source.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::sync::{Mutex, OnceLock};
+
+#[derive(PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+/// Names the interpreter itself refers to, interned up front in this order
+/// so it can match on them without going through the table.
+const PREDEFINED: &[&str] = &[
+    "this", "super", "init", "message", "kind", "filter", "has", "insert", "keys", "len", "map",
+    "pop", "push", "reduce", "remove", "slice", "sort", "values",
+];
+
+impl Symbol {
+    pub const THIS: Symbol = Symbol(0);
+    pub const SUPER: Symbol = Symbol(1);
+    pub const INIT: Symbol = Symbol(2);
+    pub const MESSAGE: Symbol = Symbol(3);
+    pub const KIND: Symbol = Symbol(4);
+    // List and map methods.
+    pub const FILTER: Symbol = Symbol(5);
+    pub const HAS: Symbol = Symbol(6);
+    pub const INSERT: Symbol = Symbol(7);
+    pub const KEYS: Symbol = Symbol(8);
+    pub const LEN: Symbol = Symbol(9);
+    pub const MAP: Symbol = Symbol(10);
+    pub const POP: Symbol = Symbol(11);
+    pub const PUSH: Symbol = Symbol(12);
+    pub const REDUCE: Symbol = Symbol(13);
+    pub const REMOVE: Symbol = Symbol(14);
+    pub const SLICE: Symbol = Symbol(15);
+    pub const SORT: Symbol = Symbol(16);
+    pub const VALUES: Symbol = Symbol(17);
+
+    pub fn intern(name: &str) -> Symbol {
+        let mut interner = interner().lock().unwrap();
+        if let Some(&symbol) = interner.symbols.get(name) {
+            return symbol;
+        }
+
+        let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+        let symbol = Symbol(interner.names.len() as u32);
+        interner.names.push(name);
+        interner.symbols.insert(name, symbol);
+        symbol
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        interner().lock().unwrap().names[self.0 as usize]
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Debug for Symbol {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+struct Interner {
+    symbols: HashMap<&'static str, Symbol>,
+    names: Vec<&'static str>,
+}
+
+fn interner() -> &'static Mutex<Interner> {
+    static INTERNER: OnceLock<Mutex<Interner>> = OnceLock::new();
+    INTERNER.get_or_init(|| {
+        let mut interner = Interner {
+            symbols: HashMap::new(),
+            names: vec![],
+        };
+        for (i, &name) in PREDEFINED.iter().enumerate() {
+            interner.symbols.insert(name, Symbol(i as u32));
+            interner.names.push(name);
+        }
+        Mutex::new(interner)
+    })
+}
@@ -0,0 +1,62 @@
+extern crate catlox;
+
+use catlox::cst;
+use catlox::lexer::{Lexer, SpannedToken};
+use catlox::parser::Parser;
+use std::fs;
+
+/// Inputs the parser has to recover from, the tree must still cover them.
+const MALFORMED: &[&str] = &[
+    "",
+    "   // only trivia\n/// and a dangling doc comment\n",
+    "let x = \"unterminated\n",
+    "/* unterminated comment\nlet a = 1;",
+    "fn f( {\n    let y = 1\n}\n",
+    ")))]}} let a = @;\nclass { fn }\n",
+    "if (a) { while (b) { x = ; } } else print(1)",
+    "print(\"a ${ 1 + } b\"); try { } ; x ?? y ? 1 : 2 = 3;",
+    "fn (a, ...b, c) => a; f(a: 1, 2);",
+];
+
+fn fixtures() -> Vec<(String, String)> {
+    let mut sources = vec![];
+    for entry in fs::read_dir("fixtures").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension() == Some("cbox".as_ref()) {
+            let source = fs::read_to_string(&path).unwrap();
+            sources.push((path.display().to_string(), source));
+        }
+    }
+    assert!(!sources.is_empty());
+    sources
+}
+
+fn sources() -> Vec<(String, String)> {
+    let mut sources = fixtures();
+    for (i, source) in MALFORMED.iter().enumerate() {
+        sources.push((format!("malformed #{}", i), source.to_string()));
+    }
+    sources
+}
+
+#[test]
+fn round_trips_source() {
+    for (name, source) in sources() {
+        assert_eq!(cst::parse(&source).to_string(), source, "{}", name);
+    }
+}
+
+#[test]
+fn lowers_to_what_the_parser_gives() {
+    for (name, source) in sources() {
+        let tokens: Vec<SpannedToken> = Lexer::new(&source).collect();
+        let parsed = Parser::new(&tokens, 0).parse();
+        let lowered = cst::parse(&source).lower(0);
+        assert_eq!(
+            format!("{:?}", lowered),
+            format!("{:?}", parsed),
+            "{}",
+            name
+        );
+    }
+}
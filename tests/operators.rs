@@ -0,0 +1,56 @@
+extern crate catlox;
+
+use catlox::interpreter::Interpreter;
+use catlox::lexer::{Lexer, SpannedToken};
+use catlox::parser::Parser;
+use catlox::resolver::resolve;
+use std::cell::RefCell;
+use std::fs;
+use std::rc::Rc;
+
+/// Run a fixture and return the lines it printed.
+fn run(path: &str) -> Vec<String> {
+    let source = fs::read_to_string(path).unwrap();
+    let tokens: Vec<SpannedToken> = Lexer::new(&source).collect();
+    let (_, statements) = Parser::new(&tokens, 0).parse().unwrap();
+
+    let output = Rc::new(RefCell::new(vec![]));
+    let printed = output.clone();
+    let mut interpreter = Interpreter::new(Box::new(move |line| {
+        printed.borrow_mut().push(line.to_string())
+    }));
+    resolve(&statements, &mut interpreter).unwrap();
+    interpreter.interpret(&statements).unwrap();
+
+    let lines = output.borrow().clone();
+    lines
+}
+
+#[test]
+fn operators_follow_the_precedence_table() {
+    // `**` is right associative and binds tighter than unary minus on its
+    // left, so `2 ** 3 ** 2` is 512 and `-2 ** 2` is -4.
+    assert_eq!(
+        run("fixtures/operators.cbox"),
+        vec!["1", "-1", "1.5", "1024", "512", "-4", "4", "0.5", "4", "8", "true"]
+    );
+}
+
+#[test]
+fn compound_assignment_evaluates_its_target_once() {
+    // `xs[next()] *= 2` calls `next` once and doubles `xs[0]`.
+    assert_eq!(
+        run("fixtures/compound_assignment.cbox"),
+        vec![
+            "2",
+            "\"hello, world\"",
+            "\"6 5\"",
+            "10",
+            "[11, 2, 2]",
+            "[22, 2, 2]",
+            "1",
+            "{\"apples\": 7}",
+            "4",
+        ]
+    );
+}